@@ -0,0 +1,142 @@
+//! A size-bounded cache of rendered page buffers, keyed by
+//! `(PageNum, RenderConfig)`, sitting above the FFI layer so re-rendering
+//! the same page at the same settings can skip [crate::extractor::bridge::render_page]
+//! entirely. Sharded so concurrent page tasks don't serialize on a single
+//! lock, the same idea as a page cache in an embedded database applied to
+//! rasterized pages.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::RwLock;
+
+use crate::extractor::{PageNum, RenderConfig};
+
+const SHARD_COUNT: usize = 16;
+
+/// A cached rasterization of one page at one [RenderConfig].
+#[derive(Clone)]
+pub struct CachedPage {
+    pub bytes: Vec<u8>,
+    pub width: i32,
+    pub height: i32,
+    pub channels: i32,
+}
+
+impl CachedPage {
+    fn byte_size(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+/// Controls whether [PageCache] is consulted at all, and how much memory it
+/// may hold before evicting the least-recently-used entry. Disabled by
+/// default so one-shot batch extraction doesn't pay for bookkeeping it'll
+/// never benefit from; a paging viewer should enable it to keep hot pages
+/// resident across re-renders.
+#[derive(Clone, Copy, Debug)]
+pub struct PageCacheConfig {
+    pub enabled: bool,
+    /// Total byte budget across all shards. Split evenly per shard, so the
+    /// effective per-shard budget is `max_bytes / SHARD_COUNT`.
+    pub max_bytes: usize,
+}
+
+impl Default for PageCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+type CacheKey = (PageNum, RenderConfig);
+
+struct Shard {
+    entries: HashMap<CacheKey, CachedPage>,
+    /// Keys ordered oldest-to-newest by last access; a hit moves its key to
+    /// the back.
+    order: Vec<CacheKey>,
+    bytes: usize,
+}
+
+impl Shard {
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(evicted) = self.entries.remove(key) {
+            self.bytes -= evicted.byte_size();
+            self.order.retain(|k| k != key);
+        }
+    }
+}
+
+/// A sharded, size-bounded LRU cache of rendered page buffers. See the
+/// module docs for the caching strategy.
+pub struct PageCache {
+    config: PageCacheConfig,
+    shards: Vec<RwLock<Shard>>,
+}
+
+impl PageCache {
+    pub fn new(config: PageCacheConfig) -> Self {
+        let shards = (0..SHARD_COUNT)
+            .map(|_| {
+                RwLock::new(Shard {
+                    entries: HashMap::new(),
+                    order: Vec::new(),
+                    bytes: 0,
+                })
+            })
+            .collect();
+
+        Self { config, shards }
+    }
+
+    pub fn config(&self) -> PageCacheConfig {
+        self.config
+    }
+
+    fn shard_for(&self, key: &CacheKey) -> &RwLock<Shard> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        key.hash(&mut hasher);
+        &self.shards[(hasher.finish() as usize) % self.shards.len()]
+    }
+
+    /// Looks up `page` rendered under `render_config`, marking it
+    /// most-recently-used on a hit. Always misses while disabled.
+    pub fn get(&self, page: PageNum, render_config: RenderConfig) -> Option<CachedPage> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let key = (page, render_config);
+        let mut shard = self.shard_for(&key).write().unwrap();
+
+        let entry = shard.entries.get(&key).cloned()?;
+        shard.order.retain(|k| k != &key);
+        shard.order.push(key);
+        Some(entry)
+    }
+
+    /// Inserts `entry` for `(page, render_config)`, evicting the
+    /// least-recently-used entries in the same shard until it fits within
+    /// its share of [PageCacheConfig::max_bytes]. A no-op while disabled.
+    pub fn insert(&self, page: PageNum, render_config: RenderConfig, entry: CachedPage) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let key = (page, render_config);
+        let mut shard = self.shard_for(&key).write().unwrap();
+        shard.remove(&key);
+
+        let shard_budget = self.config.max_bytes / SHARD_COUNT;
+        while !shard.order.is_empty() && shard.bytes + entry.byte_size() > shard_budget {
+            let oldest = shard.order.remove(0);
+            shard.remove(&oldest);
+        }
+
+        shard.bytes += entry.byte_size();
+        shard.order.push(key.clone());
+        shard.entries.insert(key, entry);
+    }
+}
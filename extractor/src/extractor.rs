@@ -1,7 +1,12 @@
 #![allow(unused)]
 
+use crate::cache::{CachedPage, PageCache, PageCacheConfig};
 use crate::extractor::bridge::PDFHandle;
+use crate::resume::ResumeState;
+use async_stream::try_stream;
 use cxx::let_cxx_string;
+use futures::Stream;
+use std::collections::BinaryHeap;
 use std::sync::{Arc, Mutex};
 use std::{
     os::raw::c_void,
@@ -9,10 +14,14 @@ use std::{
     ptr::{self, null_mut},
     slice::from_raw_parts,
     thread::available_parallelism,
+    time::{Duration, Instant},
 };
 use tokio::{
     select,
-    sync::mpsc::{Receiver, Sender},
+    sync::{
+        broadcast,
+        mpsc::{Receiver, Sender},
+    },
     task::{JoinError, JoinSet},
 };
 
@@ -22,6 +31,33 @@ use log::error;
 #[cxx::bridge]
 #[allow(unused)]
 mod bridge {
+    /// Mirrors [super::OutputFormat] across the FFI boundary; `Jpeg`'s
+    /// quality travels alongside in [RenderOptions::jpeg_quality] since cxx
+    /// shared enums can't carry payloads.
+    enum OutputFormat {
+        Png,
+        Jpeg,
+        WebP,
+        RawRgba,
+    }
+
+    /// Mirrors [super::ColorSpace] across the FFI boundary.
+    enum ColorSpace {
+        Rgb,
+        Gray,
+        Cmyk,
+    }
+
+    /// The cxx-shared form of [super::RenderConfig]. `max_dimension` of `0`
+    /// means "no bound", since cxx shared structs can't carry an `Option`.
+    struct RenderOptions {
+        scale: f32,
+        format: OutputFormat,
+        jpeg_quality: u8,
+        colorspace: ColorSpace,
+        max_dimension: u32,
+    }
+
     unsafe extern "C++" {
         include!("D:/coding/synco/pdf_parser_v3/extractor/src_cpp/main.h");
         type PDFHandle;
@@ -35,13 +71,14 @@ mod bridge {
 
         unsafe fn render_page(
             page_num: i32,
+            options: RenderOptions,
             size_buf: *mut usize,
             width_buf: *mut i32,
             height_buf: *mut i32,
             channels_buf: *mut i32,
             doc_handle: *mut PDFHandle,
             ctx_handle: *mut PDFHandle,
-        ) -> Result<*mut u8>; // bytes in PNG format to a picture of the page
+        ) -> Result<*mut u8>; // bytes in the requested format/colorspace of a picture of the page
 
         unsafe fn free_image_data(data: *mut u8);
 
@@ -59,9 +96,126 @@ mod bridge {
     }
 }
 
+/// The pixel format a rendered page's bytes are returned in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    Png,
+    Jpeg { quality: u8 },
+    WebP,
+    RawRgba,
+}
+
+/// The color space a rendered page is rasterized into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    Rgb,
+    Gray,
+    Cmyk,
+}
+
+/// Controls how a page is rasterized by `bridge::render_page`: at what
+/// resolution, in which pixel format, and in which color space. Threaded
+/// from `Extractor::new`/`iter_pages` through `spawn_page` and `iter_page`,
+/// so the same extractor can generate cheap thumbnails or high-res rasters
+/// on demand.
+#[derive(Clone, Copy, Debug)]
+pub struct RenderConfig {
+    /// Scale factor applied to the page's native size (e.g. `2.0` for
+    /// double-resolution rendering), analogous to target DPI.
+    pub scale: f32,
+    pub format: OutputFormat,
+    pub colorspace: ColorSpace,
+    /// Caps the longest edge of the rendered image, downscaling
+    /// proportionally so a thumbnail grid has a bounded size. `None` leaves
+    /// the `scale`d dimensions untouched.
+    pub max_dimension: Option<u32>,
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            format: OutputFormat::Png,
+            colorspace: ColorSpace::Rgb,
+            max_dimension: None,
+        }
+    }
+}
+
+// Manual `PartialEq`/`Eq`/`Hash` since `f32` doesn't derive either; `scale`
+// is compared/hashed bitwise, which is fine for a cache key (the same
+// `RenderConfig` value always produces the same bits) even though it isn't
+// a numerically meaningful equality for `f32` in general. This is what
+// makes `RenderConfig` usable as half of `cache::CacheKey` — without it,
+// `PageCache`'s `HashMap<CacheKey, CachedPage>` wouldn't compile.
+impl PartialEq for RenderConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.scale.to_bits() == other.scale.to_bits()
+            && self.format == other.format
+            && self.colorspace == other.colorspace
+            && self.max_dimension == other.max_dimension
+    }
+}
+
+impl Eq for RenderConfig {}
+
+impl std::hash::Hash for RenderConfig {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.scale.to_bits().hash(state);
+        self.format.hash(state);
+        self.colorspace.hash(state);
+        self.max_dimension.hash(state);
+    }
+}
+
+impl RenderConfig {
+    /// Converts to the cxx-shared [bridge::RenderOptions] passed across the
+    /// FFI boundary.
+    fn to_bridge_options(self) -> bridge::RenderOptions {
+        let (format, jpeg_quality) = match self.format {
+            OutputFormat::Png => (bridge::OutputFormat::Png, 0),
+            OutputFormat::Jpeg { quality } => (bridge::OutputFormat::Jpeg, quality),
+            OutputFormat::WebP => (bridge::OutputFormat::WebP, 0),
+            OutputFormat::RawRgba => (bridge::OutputFormat::RawRgba, 0),
+        };
+
+        let colorspace = match self.colorspace {
+            ColorSpace::Rgb => bridge::ColorSpace::Rgb,
+            ColorSpace::Gray => bridge::ColorSpace::Gray,
+            ColorSpace::Cmyk => bridge::ColorSpace::Cmyk,
+        };
+
+        bridge::RenderOptions {
+            scale: self.scale,
+            format,
+            jpeg_quality,
+            colorspace,
+            max_dimension: self.max_dimension.unwrap_or(0),
+        }
+    }
+}
+
+/// An owned rendering of one page, yielded by [Extractor::render_stream].
+/// Unlike the callback-based [Extractor::iter_pages], which hands a
+/// borrowed slice straight from `bridge::free_image_data`'s caller, `data`
+/// is copied out of the FFI buffer before it's freed, so a consumer can
+/// hold onto it past the render call (e.g. across an `.await` or a
+/// `Stream` combinator).
+#[derive(Clone, Debug)]
+pub struct RenderedPage {
+    pub page: PageNum,
+    pub data: Vec<u8>,
+    pub width: i32,
+    pub height: i32,
+    pub channels: i32,
+}
+
 pub struct Extractor {
     pub doc_path: PathBuf,
     pub page_count: i32,
+    pub render_config: RenderConfig,
+    pub concurrency_config: ConcurrencyConfig,
+    page_cache: Arc<PageCache>,
     doc_handle: *mut PDFHandle,
     ctx_handle: *mut PDFHandle,
 }
@@ -111,6 +265,187 @@ pub enum ControlMessage {
     Stop,
     Pause,
     Resume,
+    /// Reorders the pages still queued for rendering to match `Vec<PageNum>`
+    /// (earliest first = highest priority), e.g. to bring a viewer's
+    /// current viewport to the front of a background render. Pages not
+    /// present keep their queue slot, but below any page that was named.
+    Reprioritize(Vec<PageNum>),
+}
+
+/// A page queued for rendering, ordered by `priority` (higher renders
+/// sooner) then by page number for a stable tie-break.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PrioritizedPage {
+    priority: i32,
+    page: PageNum,
+}
+
+impl Ord for PrioritizedPage {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.page.cmp(&self.page))
+    }
+}
+
+impl PartialOrd for PrioritizedPage {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Progress telemetry broadcast from `iter_pages`, separate from the
+/// per-page `render_callback` error channel so a logger, a TUI, and an IPC
+/// layer can each subscribe (via `Sender::subscribe`) without stealing
+/// events from one another.
+#[derive(Clone, Debug)]
+pub enum ProgressEvent {
+    Started {
+        total: i32,
+    },
+    PageCompleted {
+        page: PageNum,
+        completed: i32,
+        total: i32,
+        /// How long this page's render took, measured around the
+        /// `spawn_blocking` call in `spawn_page`.
+        elapsed: Duration,
+        /// A rolling estimate of the time remaining, derived from an
+        /// exponential moving average of per-page durations. `None` until
+        /// at least one page has rendered successfully; failed pages count
+        /// toward `completed` but not toward the average.
+        eta: Option<Duration>,
+    },
+    Paused,
+    Resumed,
+    Finished,
+}
+
+/// Smoothing factor for the per-page duration EMA driving `ProgressEvent`'s
+/// ETA: higher weighs recent pages more heavily.
+const PAGE_DURATION_EMA_ALPHA: f64 = 0.2;
+
+fn update_ema(prev: Option<Duration>, sample: Duration) -> Duration {
+    match prev {
+        Some(prev) => Duration::from_secs_f64(
+            PAGE_DURATION_EMA_ALPHA * sample.as_secs_f64()
+                + (1.0 - PAGE_DURATION_EMA_ALPHA) * prev.as_secs_f64(),
+        ),
+        None => sample,
+    }
+}
+
+/// Bounds and ceiling for [ConcurrencyController]'s feedback loop.
+/// `max_concurrent` of `None` falls back to
+/// [Extractor::calc_max_concurrent_pages]'s core-based estimate, giving
+/// callers on constrained devices a way to cap both ends explicitly.
+#[derive(Clone, Copy, Debug)]
+pub struct ConcurrencyConfig {
+    pub min_concurrent: usize,
+    pub max_concurrent: Option<usize>,
+    /// Estimated resident render memory (recent average page output size
+    /// times in-flight task count) above which the spawn budget shrinks.
+    pub memory_ceiling_bytes: usize,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            min_concurrent: 1,
+            max_concurrent: None,
+            memory_ceiling_bytes: 512 * 1024 * 1024,
+        }
+    }
+}
+
+/// A page render counts as a latency spike once it takes this many times
+/// longer than the recent average, triggering the same backoff as
+/// exceeding [ConcurrencyConfig::memory_ceiling_bytes].
+const LATENCY_SPIKE_FACTOR: f64 = 2.0;
+
+/// Smoothing factor for the per-page output size EMA backing
+/// [ConcurrencyController]'s memory estimate.
+const PAGE_SIZE_EMA_ALPHA: f64 = 0.2;
+
+/// Additive-increase/multiplicative-decrease controller keeping the live
+/// spawn budget (`target`) between [ConcurrencyConfig::min_concurrent] and
+/// the resolved max: a page that blows the memory ceiling or takes much
+/// longer than recent pages halves the budget, while an unremarkable page
+/// grows it by one. This lets a burst of heavy pages throttle parallelism
+/// instead of spawning the full budget of heavy renders at once.
+struct ConcurrencyController {
+    config: ConcurrencyConfig,
+    max_concurrent: usize,
+    target: usize,
+    ema_size: Option<f64>,
+    ema_latency: Option<Duration>,
+}
+
+impl ConcurrencyController {
+    fn new(config: ConcurrencyConfig, hard_max: usize) -> Self {
+        let max_concurrent = config
+            .max_concurrent
+            .unwrap_or(hard_max)
+            .min(hard_max)
+            .max(config.min_concurrent.max(1));
+
+        Self {
+            config,
+            max_concurrent,
+            target: config.min_concurrent.max(1),
+            ema_size: None,
+            ema_latency: None,
+        }
+    }
+
+    fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Folds in a just-completed page's output size and render duration,
+    /// then grows or shrinks the spawn budget. `in_flight` is the number of
+    /// renders still running, used to project current resident memory from
+    /// the recent average page size.
+    fn record_completion(&mut self, size: usize, elapsed: Duration, in_flight: usize) {
+        let estimated_memory = self.ema_size.unwrap_or(size as f64) * in_flight as f64;
+
+        let latency_spike = self
+            .ema_latency
+            .is_some_and(|avg| elapsed.as_secs_f64() > avg.as_secs_f64() * LATENCY_SPIKE_FACTOR);
+
+        self.ema_size = Some(match self.ema_size {
+            Some(prev) => PAGE_SIZE_EMA_ALPHA * size as f64 + (1.0 - PAGE_SIZE_EMA_ALPHA) * prev,
+            None => size as f64,
+        });
+        self.ema_latency = Some(update_ema(self.ema_latency, elapsed));
+
+        if estimated_memory > self.config.memory_ceiling_bytes as f64 || latency_spike {
+            self.target = (self.target / 2).max(self.config.min_concurrent.max(1));
+        } else if self.target < self.max_concurrent {
+            self.target += 1;
+        }
+    }
+}
+
+/// Reorders `queue` live so pages named in `pages` render first, in the
+/// order given; any page already in the queue but not named keeps its slot,
+/// below every named page.
+fn reprioritize(queue: &mut BinaryHeap<PrioritizedPage>, pages: &[PageNum]) {
+    // Highest priority first, i.e. the order the queue would have popped in.
+    let remaining: Vec<PageNum> = std::mem::take(queue)
+        .into_sorted_vec()
+        .into_iter()
+        .rev()
+        .map(|p| p.page)
+        .collect();
+
+    for (rank, page) in remaining.into_iter().enumerate() {
+        let priority = match pages.iter().position(|p| *p == page) {
+            Some(idx) => i32::MAX - idx as i32,
+            None => -(rank as i32),
+        };
+        queue.push(PrioritizedPage { priority, page });
+    }
 }
 
 macro_rules! debug {
@@ -132,6 +467,42 @@ pub type ImageChannels = i32;
 
 impl Extractor {
     pub fn new(doc_path: impl AsRef<Path>) -> Self {
+        Self::with_render_config(doc_path, RenderConfig::default())
+    }
+
+    pub fn with_render_config(doc_path: impl AsRef<Path>, render_config: RenderConfig) -> Self {
+        Self::with_page_cache_config(doc_path, render_config, PageCacheConfig::default())
+    }
+
+    /// Like [Extractor::with_render_config], but also controls the
+    /// size-bounded cache of rendered page buffers consulted by `iter_page`
+    /// before calling into the FFI layer. Disabled by default (see
+    /// [PageCacheConfig::default]); a paging viewer re-rendering the same
+    /// pages at the same [RenderConfig] should enable it, while batch
+    /// one-shot extraction should leave it off.
+    pub fn with_page_cache_config(
+        doc_path: impl AsRef<Path>,
+        render_config: RenderConfig,
+        page_cache_config: PageCacheConfig,
+    ) -> Self {
+        Self::with_concurrency_config(
+            doc_path,
+            render_config,
+            page_cache_config,
+            ConcurrencyConfig::default(),
+        )
+    }
+
+    /// Like [Extractor::with_page_cache_config], but also controls the
+    /// min/max bounds and memory ceiling of the adaptive concurrency
+    /// feedback loop `spawn_tasks`/`spawn_render_task` use in place of a
+    /// fixed, core-count-derived budget. See [ConcurrencyConfig].
+    pub fn with_concurrency_config(
+        doc_path: impl AsRef<Path>,
+        render_config: RenderConfig,
+        page_cache_config: PageCacheConfig,
+        concurrency_config: ConcurrencyConfig,
+    ) -> Self {
         let mut doc_handle: *mut c_void = ptr::null_mut();
         let mut ctx_handle: *mut c_void = ptr::null_mut();
         let mut page_count: i32 = 0;
@@ -162,6 +533,9 @@ impl Extractor {
             doc_handle: doc_handle as *mut _ as *mut PDFHandle,
             ctx_handle: ctx_handle as *mut _ as *mut PDFHandle,
             page_count,
+            render_config,
+            concurrency_config,
+            page_cache: Arc::new(PageCache::new(page_cache_config)),
         };
 
         debug!(
@@ -171,12 +545,65 @@ impl Extractor {
         result
     }
 
+    pub fn page_cache_config(&self) -> PageCacheConfig {
+        self.page_cache.config()
+    }
+
+    /// Swaps in a freshly configured, empty page cache, e.g. to enable
+    /// caching once a batch extraction finishes and a viewer takes over.
+    pub fn set_page_cache_config(&mut self, config: PageCacheConfig) {
+        self.page_cache = Arc::new(PageCache::new(config));
+    }
+
     pub async unsafe fn iter_pages<F, State>(
         &mut self,
         callback: F,
         render_callback: Sender<Result<(), PageRenderError>>,
         state: Arc<Mutex<State>>,
+        controller: Receiver<ControlMessage>,
+        resume: Arc<Mutex<ResumeState>>,
+        checkpoint_sender: Sender<ResumeState>,
+        progress: broadcast::Sender<ProgressEvent>,
+    ) -> ()
+    where
+        F: 'static
+            + Fn(PageNum, &[u8], ImageWidth, ImageHeight, ImageChannels, Arc<Mutex<State>>) -> ()
+            + Send
+            + Sync
+            + Clone
+            + Copy,
+        State: Send + 'static,
+    {
+        unsafe {
+            self.iter_pages_range(
+                0..self.page_count,
+                callback,
+                render_callback,
+                state,
+                controller,
+                resume,
+                checkpoint_sender,
+                progress,
+            )
+            .await
+        }
+    }
+
+    /// Like [Extractor::iter_pages], but renders exactly `pages` (in the
+    /// order given, highest priority first) instead of the whole document
+    /// in ascending order. A page outside `0..self.page_count` is rejected
+    /// immediately via `render_callback` with
+    /// [PageRenderError::PageDoesNotExist] rather than being queued.
+    pub async unsafe fn iter_pages_range<F, State>(
+        &mut self,
+        pages: impl IntoIterator<Item = PageNum>,
+        callback: F,
+        render_callback: Sender<Result<(), PageRenderError>>,
+        state: Arc<Mutex<State>>,
         mut controller: Receiver<ControlMessage>,
+        resume: Arc<Mutex<ResumeState>>,
+        checkpoint_sender: Sender<ResumeState>,
+        progress: broadcast::Sender<ProgressEvent>,
     ) -> ()
     where
         F: 'static
@@ -189,10 +616,42 @@ impl Extractor {
     {
         debug!("Iterating over pages {}", self.page_count);
 
-        let mut pool: JoinSet<()> = JoinSet::new();
-        let mut pages_spawned = 0;
+        {
+            let mut guard = resume.lock().unwrap();
+            if !guard.is_valid_for(&self.doc_path) {
+                debug!("Resume checkpoint missing or stale for this document, starting fresh");
+                if let Ok(fresh) = ResumeState::new(&self.doc_path) {
+                    *guard = fresh;
+                }
+            }
+        }
+
+        let mut queue: BinaryHeap<PrioritizedPage> = BinaryHeap::new();
+        let mut total = 0;
+        for (idx, page) in pages.into_iter().enumerate() {
+            if page < 0 || page >= self.page_count {
+                render_callback
+                    .send(Err(PageRenderError::PageDoesNotExist))
+                    .await
+                    .ok();
+                continue;
+            }
+
+            // Earlier entries in the caller's order render first.
+            queue.push(PrioritizedPage {
+                priority: i32::MAX - idx as i32,
+                page,
+            });
+            total += 1;
+        }
+
+        progress.send(ProgressEvent::Started { total }).ok();
+
+        let mut pool: JoinSet<(PageNum, bool, Duration, usize)> = JoinSet::new();
         let mut pages_completed = 0;
-        let max_concurrent_pages = self.calc_max_concurrent_pages();
+        let mut ema_page_duration: Option<Duration> = None;
+        let mut concurrency =
+            ConcurrencyController::new(self.concurrency_config, self.calc_max_concurrent_pages());
 
         loop {
             select! {
@@ -202,28 +661,37 @@ impl Extractor {
                         Some(ControlMessage::Stop) => {
                             debug!("Received stop signal, cancelling remaining tasks");
                             pool.abort_all();
+                            checkpoint_sender.send(resume.lock().unwrap().clone()).await.ok();
                             break;
                         }
                         Some(ControlMessage::Pause) => {
                             debug!("Received pause signal, waiting...");
+                            progress.send(ProgressEvent::Paused).ok();
 
                             loop {
                                 match controller.recv().await {
                                     Some(ControlMessage::Resume) => {
                                         debug!("Received resume signal, continuing...");
+                                        progress.send(ProgressEvent::Resumed).ok();
                                         break;
                                     }
                                     Some(ControlMessage::Stop) => {
                                         debug!("Received stop signal while paused, halting");
                                         pool.abort_all();
+                                        checkpoint_sender.send(resume.lock().unwrap().clone()).await.ok();
                                         return;
                                     }
                                     Some(ControlMessage::Pause) => {
                                         debug!("Already paused, ignoring additional pause signal");
                                     }
+                                    Some(ControlMessage::Reprioritize(pages)) => {
+                                        debug!("Reprioritizing queue while paused");
+                                        reprioritize(&mut queue, &pages);
+                                    }
                                     None => {
                                         debug!("Control channel closed while paused");
                                         pool.abort_all();
+                                        checkpoint_sender.send(resume.lock().unwrap().clone()).await.ok();
                                         return;
                                     }
                                 }
@@ -232,6 +700,10 @@ impl Extractor {
                         Some(ControlMessage::Resume) => {
                             debug!("Received resume signal while not paused, ignoring");
                         }
+                        Some(ControlMessage::Reprioritize(pages)) => {
+                            debug!("Reprioritizing remaining queue");
+                            reprioritize(&mut queue, &pages);
+                        }
                         None => {
                             debug!("Control channel closed, finishing remaining tasks");
                         }
@@ -239,19 +711,20 @@ impl Extractor {
                 }
 
                 // spawn new page tasks if we have capacity and more pages to process
-                _ = async {}, if pages_spawned < self.page_count && pool.len() < max_concurrent_pages => {
+                _ = async {}, if !queue.is_empty() && pool.len() < concurrency.target() => {
                     // Try to keep the pipeline full by spawning multiple tasks at once for better I/O overlap
-                    unsafe { self.spawn_tasks(&mut pages_spawned, callback, render_callback.clone(), state.clone(), &mut pool) };
+                    unsafe { self.spawn_tasks(&mut queue, &mut pages_completed, callback, render_callback.clone(), state.clone(), &mut pool, &resume, concurrency.target()) };
                 }
 
                 // wait for task completion
                 result = pool.join_next(), if !pool.is_empty() => {
-                    self.handle_task_completion(&mut pages_completed, result);
+                    self.handle_task_completion(&mut pages_completed, total, &resume, &progress, &mut ema_page_duration, &mut concurrency, pool.len(), result);
                 }
 
-                // all pages were spawned and completed.
-                _ = async {}, if pages_spawned >= self.page_count && pool.is_empty() => {
+                // all queued pages were spawned and completed.
+                _ = async {}, if queue.is_empty() && pool.is_empty() => {
                     debug!("All pages completed!");
+                    progress.send(ProgressEvent::Finished).ok();
                     break;
                 }
             }
@@ -260,6 +733,186 @@ impl Extractor {
         debug!("Done iterating over pages!");
     }
 
+    /// A pull-based alternative to [Extractor::iter_pages]: renders the
+    /// whole document under `render_config`, yielding each [RenderedPage]
+    /// (or the [PageRenderError] that aborted it) through a `Stream`
+    /// instead of an `Fn` callback. This suits consumers that want to
+    /// `.await` each page, apply backpressure, or compose with other async
+    /// combinators, without needing a `'static` callback/state pair.
+    ///
+    /// Internally this reuses the same clone-per-thread/`JoinSet` machinery
+    /// and [ConcurrencyController] feedback loop as [Extractor::iter_pages_range];
+    /// `ControlMessage::Pause` simply stops polling new work until
+    /// `Resume`, and `ControlMessage::Stop` aborts in-flight tasks and ends
+    /// the stream.
+    pub fn render_stream(
+        &mut self,
+        render_config: RenderConfig,
+        mut controller: Receiver<ControlMessage>,
+    ) -> impl Stream<Item = Result<RenderedPage, PageRenderError>> + '_ {
+        try_stream! {
+            let mut queue: BinaryHeap<PrioritizedPage> = (0..self.page_count)
+                .map(|page| PrioritizedPage {
+                    priority: i32::MAX - page,
+                    page,
+                })
+                .collect();
+
+            let mut pool: JoinSet<(PageNum, Result<RenderedPage, PageRenderError>, Duration)> = JoinSet::new();
+            let mut concurrency =
+                ConcurrencyController::new(self.concurrency_config, self.calc_max_concurrent_pages());
+
+            loop {
+                select! {
+                    msg = controller.recv() => {
+                        match msg {
+                            Some(ControlMessage::Stop) => {
+                                debug!("Received stop signal, cancelling remaining render tasks");
+                                pool.abort_all();
+                                break;
+                            }
+                            Some(ControlMessage::Pause) => {
+                                debug!("Received pause signal, pausing new work...");
+                                loop {
+                                    match controller.recv().await {
+                                        Some(ControlMessage::Resume) => {
+                                            debug!("Received resume signal, continuing...");
+                                            break;
+                                        }
+                                        Some(ControlMessage::Stop) => {
+                                            debug!("Received stop signal while paused, halting");
+                                            pool.abort_all();
+                                            return;
+                                        }
+                                        Some(ControlMessage::Pause) => {
+                                            debug!("Already paused, ignoring additional pause signal");
+                                        }
+                                        Some(ControlMessage::Reprioritize(pages)) => {
+                                            debug!("Reprioritizing queue while paused");
+                                            reprioritize(&mut queue, &pages);
+                                        }
+                                        None => {
+                                            debug!("Control channel closed while paused");
+                                            pool.abort_all();
+                                            return;
+                                        }
+                                    }
+                                }
+                            }
+                            Some(ControlMessage::Resume) => {
+                                debug!("Received resume signal while not paused, ignoring");
+                            }
+                            Some(ControlMessage::Reprioritize(pages)) => {
+                                debug!("Reprioritizing remaining queue");
+                                reprioritize(&mut queue, &pages);
+                            }
+                            None => {
+                                debug!("Control channel closed, finishing remaining tasks");
+                            }
+                        }
+                    }
+
+                    _ = async {}, if !queue.is_empty() && pool.len() < concurrency.target() => {
+                        unsafe { self.spawn_render_task(&mut queue, render_config, &mut pool) };
+                    }
+
+                    result = pool.join_next(), if !pool.is_empty() => {
+                        let in_flight = pool.len();
+                        match result {
+                            Some(Ok((_, Ok(rendered), elapsed))) => {
+                                concurrency.record_completion(rendered.data.len(), elapsed, in_flight);
+                                yield rendered;
+                            }
+                            Some(Ok((_, Err(e), _))) => Err::<(), PageRenderError>(e)?,
+                            Some(Err(e)) if e.is_cancelled() => {
+                                debug!("Render task was cancelled");
+                            }
+                            #[allow(unused)]
+                            Some(Err(e)) => {
+                                #[cfg(feature = "logging")]
+                                error!("Render task failed: {}", e);
+                            }
+                            None => unreachable!("Join set should never be unexpectedly empty!"),
+                        }
+                    }
+
+                    _ = async {}, if queue.is_empty() && pool.is_empty() => {
+                        debug!("All pages rendered!");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    unsafe fn spawn_render_task(
+        &self,
+        queue: &mut BinaryHeap<PrioritizedPage>,
+        render_config: RenderConfig,
+        pool: &mut JoinSet<(PageNum, Result<RenderedPage, PageRenderError>, Duration)>,
+    ) {
+        let page = match queue.pop() {
+            Some(prioritized) => prioritized.page,
+            None => return,
+        };
+
+        let thread_ctx_addr = match unsafe { self.clone_ctx() } {
+            Ok(ctx) => ctx as MemAddress,
+            Err(e) => {
+                pool.spawn(
+                    async move { (page, Err(PageRenderError::Unexpected(e)), Duration::ZERO) },
+                );
+                return;
+            }
+        };
+
+        let thread_doc_addr = match unsafe { self.clone_doc(thread_ctx_addr as *mut PDFHandle) } {
+            Ok(doc) => doc as MemAddress,
+            Err(e) => {
+                unsafe {
+                    bridge::cleanup_pdf(
+                        null_mut() as *mut PDFHandle,
+                        thread_ctx_addr as *mut PDFHandle,
+                    );
+                }
+                pool.spawn(
+                    async move { (page, Err(PageRenderError::Unexpected(e)), Duration::ZERO) },
+                );
+                return;
+            }
+        };
+
+        let page_cache = self.page_cache.clone();
+
+        pool.spawn(async move {
+            let started = Instant::now();
+            let result = tokio::task::spawn_blocking(move || unsafe {
+                Self::render_page_buffered(
+                    page,
+                    thread_ctx_addr,
+                    thread_doc_addr,
+                    render_config,
+                    &page_cache,
+                )
+            })
+            .await;
+            let elapsed = started.elapsed();
+
+            match result {
+                Ok(Ok(rendered)) => (page, Ok(rendered), elapsed),
+                Ok(Err(e)) => (page, Err(e), elapsed),
+                Err(join_error) => (
+                    page,
+                    Err(PageRenderError::Unexpected(format!(
+                        "Task panicked: {}",
+                        join_error
+                    ))),
+                    elapsed,
+                ),
+            }
+        });
+    }
+
     fn calc_max_concurrent_pages(&self) -> usize {
         available_parallelism()
             .map(|p| {
@@ -333,7 +986,9 @@ impl Extractor {
         thread_ctx_addr: MemAddress,
         thread_doc_addr: MemAddress,
         state: Arc<Mutex<State>>,
-        join_set: &mut JoinSet<()>,
+        join_set: &mut JoinSet<(PageNum, bool, Duration, usize)>,
+        render_config: RenderConfig,
+        page_cache: Arc<PageCache>,
     ) where
         F: 'static
             + Fn(i32, &[u8], i32, i32, i32, Arc<Mutex<State>>) -> ()
@@ -347,34 +1002,29 @@ impl Extractor {
         let callback_clone = callback.clone();
 
         join_set.spawn(async move {
-            let result = tokio::task::spawn_blocking(move || {
-                let mut size: usize = 0;
-                let mut width: i32 = 0;
-                let mut height: i32 = 0;
-                let mut channels: i32 = 0;
-
-                unsafe {
-                    Self::iter_page(
-                        &mut size,
-                        &mut width,
-                        &mut height,
-                        &mut channels,
-                        page,
-                        callback_clone,
-                        state,
-                        thread_ctx_addr,
-                        thread_doc_addr,
-                    )
-                }
+            let started = Instant::now();
+            let result = tokio::task::spawn_blocking(move || unsafe {
+                Self::iter_page(
+                    page,
+                    callback_clone,
+                    state,
+                    thread_ctx_addr,
+                    thread_doc_addr,
+                    render_config,
+                    page_cache,
+                )
             })
             .await;
+            let elapsed = started.elapsed();
 
             match result {
-                Ok(Ok(())) => {
+                Ok(Ok(size)) => {
                     debug!("Page {} processed successfully", page);
+                    (page, true, elapsed, size)
                 }
                 Ok(Err(e)) => {
                     render_callback_clone.send(Err(e)).await.ok();
+                    (page, false, elapsed, 0)
                 }
                 Err(join_error) => {
                     render_callback_clone
@@ -384,22 +1034,21 @@ impl Extractor {
                         ))))
                         .await
                         .ok();
+                    (page, false, elapsed, 0)
                 }
             }
         });
     }
 
     unsafe fn iter_page<F, State>(
-        size: &mut usize,
-        width: &mut i32,
-        height: &mut i32,
-        channels: &mut i32,
         page: i32,
         callback: F,
         state: Arc<Mutex<State>>,
         ctx_handle: MemAddress,
         doc_handle: MemAddress,
-    ) -> Result<(), PageRenderError>
+        render_config: RenderConfig,
+        page_cache: Arc<PageCache>,
+    ) -> Result<usize, PageRenderError>
     where
         F: 'static
             + Fn(i32, &[u8], i32, i32, i32, Arc<Mutex<State>>) -> ()
@@ -413,6 +1062,41 @@ impl Extractor {
             page, ctx_handle, doc_handle
         );
 
+        let rendered = unsafe {
+            Self::render_page_buffered(page, ctx_handle, doc_handle, render_config, &page_cache)
+        }?;
+
+        debug!("Rendered page! calling callback function...");
+
+        let size = rendered.data.len();
+
+        callback(
+            page,
+            &rendered.data,
+            rendered.width,
+            rendered.height,
+            rendered.channels,
+            state,
+        );
+
+        debug!("Successfully called callback function!");
+
+        Ok(size)
+    }
+
+    /// The FFI-rendering core shared by the callback-based [Extractor::iter_page]
+    /// and the `Stream`-based [Extractor::render_stream]: consults `page_cache`,
+    /// falls back to `bridge::render_page` on a miss, and always returns an
+    /// owned buffer with `ctx_handle`/`doc_handle` already cleaned up (a
+    /// cache hit never needed the cloned handles; a miss is done with them
+    /// once the bytes are copied out).
+    unsafe fn render_page_buffered(
+        page: i32,
+        ctx_handle: MemAddress,
+        doc_handle: MemAddress,
+        render_config: RenderConfig,
+        page_cache: &PageCache,
+    ) -> Result<RenderedPage, PageRenderError> {
         // Validate handles
         if ctx_handle == 0 {
             return Err(PageRenderError::InvalidContextHandle);
@@ -424,6 +1108,34 @@ impl Extractor {
             ));
         }
 
+        // Cleanup function for both context and document
+        unsafe fn cleanup_ctx_and_doc(ctx_handle: MemAddress, doc_handle: MemAddress) {
+            unsafe {
+                bridge::cleanup_pdf(doc_handle as *mut PDFHandle, ctx_handle as *mut PDFHandle)
+            };
+        }
+
+        if let Some(cached) = page_cache.get(page, render_config) {
+            debug!(
+                "Page {} served from the page cache, skipping FFI render",
+                page
+            );
+            unsafe { cleanup_ctx_and_doc(ctx_handle, doc_handle) };
+
+            return Ok(RenderedPage {
+                page,
+                data: cached.bytes,
+                width: cached.width,
+                height: cached.height,
+                channels: cached.channels,
+            });
+        }
+
+        let mut size: usize = 0;
+        let mut width: i32 = 0;
+        let mut height: i32 = 0;
+        let mut channels: i32 = 0;
+
         let image = unsafe {
             // Convert MemAddress values back to PDFHandle values and create stack variables
             let ctx_handle_value: *mut c_void = ctx_handle as *mut c_void;
@@ -436,10 +1148,11 @@ impl Extractor {
 
             bridge::render_page(
                 page,
-                size as *mut usize,
-                width as *mut i32,
-                height as *mut i32,
-                channels as *mut i32,
+                render_config.to_bridge_options(),
+                &mut size as *mut usize,
+                &mut width as *mut i32,
+                &mut height as *mut i32,
+                &mut channels as *mut i32,
                 &doc_handle_value as *const *mut c_void as *mut PDFHandle,
                 &ctx_handle_value as *const *mut c_void as *mut PDFHandle,
             )
@@ -447,13 +1160,6 @@ impl Extractor {
 
         debug!("Attempted to render page from FFI!");
 
-        // Cleanup function for both context and document
-        unsafe fn cleanup_ctx_and_doc(ctx_handle: MemAddress, doc_handle: MemAddress) {
-            unsafe {
-                bridge::cleanup_pdf(doc_handle as *mut PDFHandle, ctx_handle as *mut PDFHandle)
-            };
-        }
-
         let image = match image {
             Ok(i) => i,
             Err(e) => {
@@ -491,13 +1197,21 @@ impl Extractor {
 
         debug!("Rendered page!");
 
-        let image_slice = unsafe { from_raw_parts(image, *size) };
-
-        debug!("Converted page to a slice! calling callback function...");
-
-        callback(page, image_slice, *width, *height, *channels, state);
+        let image_slice = unsafe { from_raw_parts(image, size) };
+        let data = image_slice.to_vec();
+
+        page_cache.insert(
+            page,
+            render_config,
+            CachedPage {
+                bytes: data.clone(),
+                width,
+                height,
+                channels,
+            },
+        );
 
-        debug!("Successfully called callback function! freeing image data.");
+        debug!("Copied page into an owned buffer, freeing FFI image data.");
 
         unsafe { bridge::free_image_data(image) };
 
@@ -515,20 +1229,56 @@ impl Extractor {
         unsafe {
             cleanup_ctx_and_doc(ctx_handle, doc_handle);
         };
-        Ok(())
+
+        Ok(RenderedPage {
+            page,
+            data,
+            width,
+            height,
+            channels,
+        })
     }
 
     fn handle_task_completion(
         &self,
         pages_completed: &mut i32,
-        result: Option<Result<(), JoinError>>,
+        total: i32,
+        resume: &Arc<Mutex<ResumeState>>,
+        progress: &broadcast::Sender<ProgressEvent>,
+        ema_page_duration: &mut Option<Duration>,
+        concurrency: &mut ConcurrencyController,
+        in_flight: usize,
+        result: Option<Result<(PageNum, bool, Duration, usize), JoinError>>,
     ) -> () {
         match result {
-            Some(Ok(())) => {
+            Some(Ok((page, succeeded, elapsed, size))) => {
                 *pages_completed += 1;
+                if succeeded {
+                    // Only recorded complete now that the render, the
+                    // callback, and `bridge::free_image_data`/cleanup have
+                    // all succeeded, so an interrupted render is retried on
+                    // resume instead of silently skipped.
+                    resume.lock().unwrap().mark_complete(page);
+                    *ema_page_duration = Some(update_ema(*ema_page_duration, elapsed));
+                    concurrency.record_completion(size, elapsed, in_flight);
+                }
+
+                let remaining = (total - *pages_completed).max(0) as u32;
+                let eta = ema_page_duration.map(|avg| avg * remaining);
+
+                progress
+                    .send(ProgressEvent::PageCompleted {
+                        page,
+                        completed: *pages_completed,
+                        total,
+                        elapsed,
+                        eta,
+                    })
+                    .ok();
+
                 debug!(
                     "Page task completed. Progress: {}/{}",
-                    pages_completed, self.page_count
+                    pages_completed, total
                 );
             }
             Some(Err(e)) if e.is_cancelled() => {
@@ -547,11 +1297,14 @@ impl Extractor {
 
     unsafe fn spawn_tasks<F, State>(
         &self,
-        pages_spawned: &mut i32,
+        queue: &mut BinaryHeap<PrioritizedPage>,
+        pages_completed: &mut i32,
         callback: F,
         render_callback: Sender<Result<(), PageRenderError>>,
         state: Arc<Mutex<State>>,
-        pool: &mut JoinSet<()>,
+        pool: &mut JoinSet<(PageNum, bool, Duration, usize)>,
+        resume: &Arc<Mutex<ResumeState>>,
+        target_concurrency: usize,
     ) -> ()
     where
         F: 'static
@@ -564,16 +1317,20 @@ impl Extractor {
     {
         // Process pages in batches for better cache locality
         const BATCH_SIZE: i32 = 4;
-        let available_slots = self.calc_max_concurrent_pages() - pool.len();
+        let available_slots = target_concurrency.saturating_sub(pool.len());
         let batch_count = std::cmp::min(available_slots, BATCH_SIZE as usize) as i32;
 
         for _ in 0..batch_count {
-            let page = *pages_spawned;
-            if page >= self.page_count {
-                return;
-            }
+            let page = match queue.pop() {
+                Some(prioritized) => prioritized.page,
+                None => return,
+            };
 
-            *pages_spawned += 1;
+            if resume.lock().unwrap().is_complete(page) {
+                debug!("Page {} already completed in a prior run, skipping", page);
+                *pages_completed += 1;
+                continue;
+            }
 
             debug!("Spawning task for page {}", page);
 
@@ -644,6 +1401,8 @@ impl Extractor {
                     thread_doc_addr,
                     state.clone(),
                     pool,
+                    self.render_config,
+                    self.page_cache.clone(),
                 )
             };
         }
@@ -6,10 +6,12 @@
 // use std::sync::{Arc, Mutex};
 // use tokio::sync::mpsc::channel;
 
+pub mod cache;
 pub mod classifier;
 pub mod config;
 pub mod extractor;
 pub mod pattern;
+pub mod resume;
 
 #[derive(Clone, Copy)]
 struct Doc;
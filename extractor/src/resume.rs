@@ -0,0 +1,77 @@
+//! Checkpointing for [crate::extractor::Extractor::iter_pages]: tracks which
+//! pages of a document have already been fully rendered, so a crash or a
+//! deliberate [crate::extractor::ControlMessage::Stop] can be resumed from
+//! instead of re-rendering the whole file.
+
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::extractor::PageNum;
+
+/// A serializable record of how far an [Extractor](crate::extractor::Extractor)
+/// got through a document, keyed by a content hash so a checkpoint from a
+/// since-changed file is never resumed against.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResumeState {
+    doc_path: PathBuf,
+    content_hash: u64,
+    completed: HashSet<PageNum>,
+}
+
+impl ResumeState {
+    /// Starts a fresh checkpoint for `doc_path`, hashing its current
+    /// contents so a later resume can detect the file changed underneath
+    /// it.
+    pub fn new(doc_path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let bytes = std::fs::read(doc_path.as_ref())?;
+
+        Ok(Self {
+            doc_path: doc_path.as_ref().to_path_buf(),
+            content_hash: hash_bytes(&bytes),
+            completed: HashSet::new(),
+        })
+    }
+
+    /// True if this checkpoint was taken against the current on-disk
+    /// contents of `doc_path`. A document that was replaced or edited since
+    /// the checkpoint was written fails this check, invalidating the whole
+    /// checkpoint rather than resuming against page numbers that may no
+    /// longer mean the same thing.
+    pub fn is_valid_for(&self, doc_path: impl AsRef<Path>) -> bool {
+        if self.doc_path != doc_path.as_ref() {
+            return false;
+        }
+
+        match std::fs::read(doc_path.as_ref()) {
+            Ok(bytes) => hash_bytes(&bytes) == self.content_hash,
+            Err(_) => false,
+        }
+    }
+
+    /// Whether `page` was already fully rendered in a prior run.
+    pub fn is_complete(&self, page: PageNum) -> bool {
+        self.completed.contains(&page)
+    }
+
+    /// Records `page` as fully rendered. Only ever called once the render
+    /// for `page` (including `bridge::free_image_data` and context/document
+    /// cleanup) has actually succeeded, so an interrupted render is retried
+    /// on the next resume instead of being silently skipped.
+    pub(crate) fn mark_complete(&mut self, page: PageNum) {
+        self.completed.insert(page);
+    }
+
+    pub fn completed_count(&self) -> usize {
+        self.completed.len()
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,153 @@
+//! Pluggable memoization of classification results, keyed by a hash of a
+//! page's rendered bytes folded together with a hash of the [`Config`] that
+//! classified it ([`cache_key`]) — the config hash folds in the registered
+//! object graph (see [`Config::cache_fingerprint`]), so a cache built
+//! against one object graph never collides with one built against another.
+//!
+//! `ClassificationResult::Confident`/`Probable` carry an arbitrary
+//! `SharedData` payload that a [`Cache`] can't serialize without knowing
+//! it's `Serialize + DeserializeOwned`. A type that implements
+//! [`CachePolicy`] to say so gets the full round-trip, so a hit skips
+//! `T::classify` entirely; any type that doesn't still gets its
+//! tier/confidence cached (useful on its own for reporting), but `classify`
+//! always reruns on a hit to reproduce `SharedData`. This split happens in
+//! [`crate::Classifier::classify_cached`].
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use pdf_struct_traits::{Classify, ConfidenceScore};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::ClassiferError;
+
+/// Identifies one cached classification: a hash of the page bytes folded
+/// together with [`Config::cache_fingerprint`].
+pub type CacheKey = u64;
+
+/// Computes the [`CacheKey`] for `page_bytes` under `config`. Reuses the
+/// same non-cryptographic hasher [`crate::incremental::fingerprint`] already
+/// uses for content fingerprints, rather than pulling in a second hashing
+/// dependency for a cache key that has no adversarial-input concerns.
+pub fn cache_key(page_bytes: &[u8], config: &Config) -> CacheKey {
+    let mut hasher = DefaultHasher::new();
+    page_bytes.hash(&mut hasher);
+    config.cache_fingerprint().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Which confidence tier a cached classification landed in, without the
+/// `SharedData`/error payload — mirrors [`pdf_struct_traits::ClassificationResult`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tier {
+    Confident,
+    Probable,
+    Uncertain,
+}
+
+/// One stored cache entry. `data` is only `Some` for a [`CachePolicy`] type
+/// that chose to serialize its `SharedData`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CacheEntry {
+    pub tier: Tier,
+    pub confidence: ConfidenceScore,
+    pub data: Option<Vec<u8>>,
+}
+
+/// Pluggable storage for [`CacheEntry`] values, selected through
+/// [`crate::config::ConfigBuilder::with_cache`].
+pub trait Cache: Send + Sync {
+    fn get(&self, key: CacheKey) -> Option<CacheEntry>;
+    fn put(&self, key: CacheKey, entry: CacheEntry);
+}
+
+/// In-memory [`Cache`]; entries live only as long as the process, good for
+/// reusing results across boilerplate pages within a single run.
+#[derive(Default)]
+pub struct MemoryCache {
+    entries: RwLock<HashMap<CacheKey, CacheEntry>>,
+}
+
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Cache for MemoryCache {
+    fn get(&self, key: CacheKey) -> Option<CacheEntry> {
+        self.entries.read().unwrap().get(&key).cloned()
+    }
+
+    fn put(&self, key: CacheKey, entry: CacheEntry) {
+        self.entries.write().unwrap().insert(key, entry);
+    }
+}
+
+/// On-disk [`Cache`] backed by a single JSON file, persisting results
+/// across process runs. Loads the whole file into memory on [`DiskCache::open`]
+/// and rewrites it on every [`Cache::put`]; fine for the page counts
+/// `pdf_struct` targets, not meant for documents with millions of pages.
+pub struct DiskCache {
+    path: PathBuf,
+    memory: MemoryCache,
+}
+
+impl DiskCache {
+    /// Loads `path` if it exists, starting empty otherwise.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, ClassiferError> {
+        let path = path.into();
+
+        let entries = match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(ClassiferError::ClassificationFailed(e.to_string())),
+        };
+
+        Ok(Self {
+            path,
+            memory: MemoryCache {
+                entries: RwLock::new(entries),
+            },
+        })
+    }
+
+    fn flush(&self) {
+        let entries = self.memory.entries.read().unwrap();
+        if let Ok(json) = serde_json::to_string_pretty(&*entries) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+impl Cache for DiskCache {
+    fn get(&self, key: CacheKey) -> Option<CacheEntry> {
+        self.memory.get(key)
+    }
+
+    fn put(&self, key: CacheKey, entry: CacheEntry) {
+        self.memory.put(key, entry);
+        self.flush();
+    }
+}
+
+/// Declares how a [`Classify`] type's `SharedData` is represented in a
+/// [`CacheEntry`]. Implement this manually (the default methods cache only
+/// the tier/confidence) for any type whose `SharedData: Serialize +
+/// DeserializeOwned`, so a cache hit can skip `classify` entirely instead of
+/// always rerunning it to reproduce `SharedData`.
+pub trait CachePolicy: Classify {
+    fn encode_shared_data(_data: &Self::SharedData) -> Option<Vec<u8>> {
+        None
+    }
+
+    fn decode_shared_data(_bytes: &[u8]) -> Option<Self::SharedData> {
+        None
+    }
+}
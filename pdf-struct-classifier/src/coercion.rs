@@ -0,0 +1,155 @@
+//! Lets classification's `SharedData` for one type feed an extraction that
+//! expects a different, but convertible, type — e.g. a parent object
+//! publishing shared data its children consume through a registered
+//! widening conversion — instead of forcing every `Object` in a subtree to
+//! agree on one concrete `SharedData` type. See [`CoercionTable::coerce`].
+//!
+//! `cast_classification`/`cast_extraction` still require an exact
+//! [`std::any::TypeId`] match on the stored function pointer; a
+//! [`CoercionTable`] operates one level up, on the boxed `SharedData` value
+//! itself, once a caller already knows (from [`pdf_struct_traits::Object`])
+//! which concrete type it needs.
+
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+
+/// A single registered conversion: takes ownership of a boxed value of the
+/// edge's `from` type and returns one of its `to` type. Only ever called
+/// with the type it was registered for — see [`CoercionTable::register`].
+/// Boxed rather than a bare `fn` pointer since [`CoercionTable::register`]
+/// needs to capture its own `convert: fn(T) -> U` into the downcast/rebox
+/// wrapper it builds.
+pub type CoercionFn = Box<dyn Fn(Box<dyn Any>) -> Box<dyn Any>>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CoercionError {
+    #[error("no coercion path from {from:?} to {to:?} within depth {max_depth}")]
+    NoPath {
+        from: TypeId,
+        to: TypeId,
+        max_depth: usize,
+    },
+    /// More than one shortest path connects `from` to `to`; picking one
+    /// over another would be a guess, so this is refused instead.
+    #[error("ambiguous coercion from {from:?} to {to:?}: {count} paths of length {length}")]
+    Ambiguous {
+        from: TypeId,
+        to: TypeId,
+        length: usize,
+        count: usize,
+    },
+}
+
+/// A registry of `from -> to` conversions between `SharedData` types.
+/// [`CoercionTable::coerce`] searches it transitively — like an
+/// autoderef/coercion chain — when no direct edge covers the pair asked
+/// for, refusing rather than guessing if that search turns up more than
+/// one equally-short path.
+#[derive(Default)]
+pub struct CoercionTable {
+    edges: HashMap<TypeId, Vec<(TypeId, CoercionFn)>>,
+}
+
+impl CoercionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a direct conversion `T -> U`.
+    pub fn register<T: 'static, U: 'static>(&mut self, convert: fn(T) -> U) {
+        let wrapped: CoercionFn = Box::new(move |value| {
+            let value = value
+                .downcast::<T>()
+                .unwrap_or_else(|_| panic!("coercion edge invoked with the wrong boxed type"));
+            Box::new(convert(*value)) as Box<dyn Any>
+        });
+
+        self.edges
+            .entry(TypeId::of::<T>())
+            .or_default()
+            .push((TypeId::of::<U>(), wrapped));
+    }
+
+    /// Converts `value` (a boxed instance of `from`) into `to`: directly if
+    /// `from == to`, via a registered edge, or otherwise via the shortest
+    /// chain of edges found by [`CoercionTable::find_path`].
+    pub fn coerce(
+        &self,
+        value: Box<dyn Any>,
+        from: TypeId,
+        to: TypeId,
+        max_depth: usize,
+    ) -> Result<Box<dyn Any>, CoercionError> {
+        if from == to {
+            return Ok(value);
+        }
+
+        let path = self.find_path(from, to, max_depth)?;
+        Ok(path
+            .into_iter()
+            .fold(value, |value, convert| convert(value)))
+    }
+
+    /// Breadth-first search for a path of registered edges from `from` to
+    /// `to`, stopping at the first depth (up to `max_depth` hops) any path
+    /// reaches `to`. If more than one path reaches `to` at that same depth,
+    /// the result is ambiguous and refused rather than picking one
+    /// arbitrarily.
+    fn find_path(
+        &self,
+        from: TypeId,
+        to: TypeId,
+        max_depth: usize,
+    ) -> Result<Vec<&CoercionFn>, CoercionError> {
+        let mut frontier: Vec<(TypeId, Vec<&CoercionFn>)> = vec![(from, Vec::new())];
+        let mut visited: HashSet<TypeId> = HashSet::from([from]);
+
+        for depth in 1..=max_depth {
+            let mut next_frontier = Vec::new();
+            let mut matches: Vec<Vec<&CoercionFn>> = Vec::new();
+
+            for (node, path) in &frontier {
+                for (next, convert) in self.edges.get(node).map(Vec::as_slice).unwrap_or(&[]) {
+                    let mut extended = path.clone();
+                    extended.push(convert);
+
+                    if *next == to {
+                        matches.push(extended.clone());
+                    } else if !visited.contains(next) {
+                        // A node reached by several paths at this depth keeps
+                        // all of them, so they surface as separate matches.
+                        next_frontier.push((*next, extended));
+                    }
+                }
+            }
+
+            for (node, _) in &next_frontier {
+                visited.insert(*node);
+            }
+
+            match matches.len() {
+                0 => {}
+                1 => return Ok(matches.into_iter().next().unwrap()),
+                count => {
+                    return Err(CoercionError::Ambiguous {
+                        from,
+                        to,
+                        length: depth,
+                        count,
+                    })
+                }
+            }
+
+            if next_frontier.is_empty() {
+                break;
+            }
+            frontier = next_frontier;
+        }
+
+        Err(CoercionError::NoPath {
+            from,
+            to,
+            max_depth,
+        })
+    }
+}
@@ -1,13 +1,50 @@
+use crate::cache::Cache;
 use crate::instances::*;
-use pdf_struct_traits::{Classify, Object, Root};
+use crate::routing::{ConfidenceThresholds, EnsembleStrategy};
+use crate::sidecar::TagRegistry;
+use crate::ClassiferError;
+use pdf_struct_traits::{Classify, Object, Pattern, Root};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fmt::{Debug, Display};
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, RwLock};
 
+/// Controls how much work `Classifier::begin`/`classify_chunk` actually do
+/// against a page, analogous to distinct compiler build modes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum ClassificationMode {
+    /// Runs the complete OCR-backed `Classify`/`Extract` path.
+    #[default]
+    Full,
+    /// Kept apart from `Full` in cache fingerprints, but
+    /// [`crate::Classifier::classify_chunk`] runs the same path for both:
+    /// every registered type's `Classify` over the page bytes. No cheaper,
+    /// OCR-free heuristic path exists for this mode.
+    Check,
+    /// Walks the page range and reports, per page, which object type
+    /// *would* be attempted and which patterns apply, without calling into
+    /// the extractor at all.
+    DryRun,
+}
+
 /// Represents the configuration for document structure.
 pub struct Config {
     pub(crate) types: Vec<Arc<RwLock<ConcretePageType>>>,
     pub(crate) root: ConcreteRoot,
+    pub(crate) root_tag: Option<String>,
     pub(crate) offset: usize,
+    pub(crate) mode: ClassificationMode,
+    pub(crate) cache: Option<Arc<dyn Cache>>,
+    pub(crate) thresholds: HashMap<String, ConfidenceThresholds>,
+    pub(crate) default_threshold: ConfidenceThresholds,
+    pub(crate) ensemble_strategy: EnsembleStrategy,
+    /// Every registered type, erased via [`Candidate`] so
+    /// [`crate::Classifier::classify_chunk`] can classify a chunk's bytes
+    /// against all of them without knowing each one's concrete
+    /// `SharedData`/error type. Populated alongside `types` by
+    /// [`ConfigBuilder::with_obj`].
+    pub(crate) candidates: Vec<Candidate<ClassiferError>>,
 }
 
 impl Config {
@@ -15,8 +52,230 @@ impl Config {
         ConfigBuilder {
             types: vec![],
             root: None,
+            root_tag: None,
             offset: 0,
+            mode: ClassificationMode::default(),
+            cache: None,
+            thresholds: HashMap::new(),
+            default_threshold: ConfidenceThresholds::default(),
+            ensemble_strategy: EnsembleStrategy::Max,
+            candidates: vec![],
+        }
+    }
+
+    /// The [`ConfidenceThresholds`] `type_tag` should be routed through, set
+    /// via [`ConfigBuilder::with_threshold`] or falling back to
+    /// [`ConfigBuilder::set_default_threshold`].
+    pub(crate) fn threshold_for(&self, type_tag: &str) -> ConfidenceThresholds {
+        self.thresholds
+            .get(type_tag)
+            .copied()
+            .unwrap_or(self.default_threshold)
+    }
+
+    /// The [`EnsembleStrategy`] [`crate::routing::combine_ensemble`] should
+    /// use when multiple `Classify` impls are run for the same candidate
+    /// type.
+    pub(crate) fn ensemble_strategy(&self) -> &EnsembleStrategy {
+        &self.ensemble_strategy
+    }
+
+    /// The [`Cache`] classification results should be memoized through, if
+    /// one was configured via [`ConfigBuilder::with_cache`].
+    pub(crate) fn cache(&self) -> Option<&Arc<dyn Cache>> {
+        self.cache.as_ref()
+    }
+
+    /// A hash of the registered object graph (every type's tag, the root's
+    /// tag, and the classification mode), folded into every
+    /// [`crate::cache::cache_key`] so a cache built against one object graph
+    /// never produces a hit against a different one.
+    pub(crate) fn cache_fingerprint(&self) -> u64 {
+        let mut tags: Vec<String> = self
+            .types
+            .iter()
+            .map(|page_type| {
+                let page_type = page_type.read().unwrap();
+                let inner = page_type.inner();
+                let obj = inner.read().unwrap();
+                obj.obj_type.ident.to_string()
+            })
+            .collect();
+        tags.sort();
+
+        let mut hasher = DefaultHasher::new();
+        tags.hash(&mut hasher);
+        self.root_tag.hash(&mut hasher);
+        self.mode.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The mode `Classifier::begin`/`classify_chunk` run under.
+    pub(crate) fn mode(&self) -> ClassificationMode {
+        self.mode
+    }
+
+    /// Every registered type, erased for `classify_chunk`'s dispatch. See
+    /// [`Config::candidates`] field docs.
+    pub(crate) fn candidates(&self) -> &[Candidate<ClassiferError>] {
+        &self.candidates
+    }
+
+    /// Builds a map from each registered type's tag to its parent/pair tags,
+    /// used by the sidecar serializer to fill in [crate::sidecar::PageRecord]
+    /// without re-walking the live tree per page.
+    pub(crate) fn type_tag_registry(&self) -> TagRegistry {
+        let mut registry = HashMap::new();
+
+        for page_type in &self.types {
+            let page_type = page_type.read().unwrap();
+            let inner = page_type.inner();
+            let obj = inner.read().unwrap();
+
+            let parent_tag = obj.parent_inner().map(|parent| {
+                let parent = parent.read().unwrap();
+                parent.obj_type.ident.to_string()
+            });
+
+            let pair_tag = if let ConcretePageType::Pair(pair) = &*page_type {
+                pair.get_pair_info().map(|other| {
+                    let other_inner = other.inner.read().unwrap();
+                    other_inner.obj_type.ident.to_string()
+                })
+            } else {
+                None
+            };
+
+            registry.insert(obj.obj_type.ident.to_string(), (parent_tag, pair_tag));
         }
+
+        registry
+    }
+
+    /// The tag of the registered root type, if any.
+    pub(crate) fn root_tag(&self) -> Option<String> {
+        self.root_tag.clone()
+    }
+
+    /// Lists every registered type's tag alongside a human-readable
+    /// description of the [pdf_struct_traits::Pattern]s it declares,
+    /// without touching the extractor or any page bytes. Used by
+    /// [ClassificationMode::DryRun] to report what *would* be attempted
+    /// against a page.
+    pub(crate) fn dry_run_candidates(&self) -> Vec<(String, Vec<String>)> {
+        self.types
+            .iter()
+            .map(|page_type| {
+                let page_type = page_type.read().unwrap();
+                let inner = page_type.inner();
+                let obj = inner.read().unwrap();
+                let tag = obj.obj_type.ident.to_string();
+
+                let patterns = if let ConcretePageType::Pair(pair) = &*page_type {
+                    pair.patterns.iter().map(describe_pattern).collect()
+                } else {
+                    Vec::new()
+                };
+
+                (tag, patterns)
+            })
+            .collect()
+    }
+
+    /// Walks every registered type (and the root, if any) into a
+    /// serializable [`crate::schema::Schema`] — a node per type plus
+    /// parent->child and pair edges — so a caller can inspect or visualize
+    /// the object graph `with_obj`/`with_root` built up, via
+    /// [`crate::schema::Schema::to_json`]/[`crate::schema::Schema::to_dot`].
+    pub fn export_schema(&self) -> crate::schema::Schema {
+        use crate::schema::{Role, SchemaNode};
+
+        let mut nodes: Vec<SchemaNode> = self
+            .types
+            .iter()
+            .map(|page_type| {
+                let page_type = page_type.read().unwrap();
+                let inner = page_type.inner();
+                let obj = inner.read().unwrap();
+
+                let role = match &*page_type {
+                    ConcretePageType::Key(_) => Role::Key,
+                    ConcretePageType::Inferred(_) => Role::Inferred,
+                    ConcretePageType::Pair(_) => Role::Pair,
+                };
+
+                let pair = if let ConcretePageType::Pair(pair) = &*page_type {
+                    pair.get_pair_info()
+                        .map(|other| other.inner.read().unwrap().obj_type.ident.to_string())
+                } else {
+                    None
+                };
+
+                let patterns = if let ConcretePageType::Pair(pair) = &*page_type {
+                    pair.patterns.iter().map(describe_pattern).collect()
+                } else {
+                    Vec::new()
+                };
+
+                SchemaNode {
+                    ident: obj.obj_type.ident.to_string(),
+                    role,
+                    children: obj
+                        .expected_children
+                        .iter()
+                        .filter(|ty| ty.ident != "()")
+                        .map(|ty| ty.ident.to_string())
+                        .collect(),
+                    pair,
+                    patterns,
+                }
+            })
+            .collect();
+
+        if let Some(root_tag) = &self.root_tag {
+            let children = self
+                .root
+                .children
+                .iter()
+                .map(|child| {
+                    let child = child.read().unwrap();
+                    let inner = child.inner();
+                    let inner = inner.read().unwrap();
+                    inner.obj_type.ident.to_string()
+                })
+                .collect();
+
+            nodes.push(SchemaNode {
+                ident: root_tag.clone(),
+                role: Role::Root,
+                children,
+                pair: None,
+                patterns: Vec::new(),
+            });
+        }
+
+        let cycles = crate::schema::detect_cycles(&nodes);
+
+        crate::schema::Schema {
+            nodes,
+            root: self.root_tag.clone(),
+            cycles,
+        }
+    }
+
+    /// Finds the tag of the registered type matching `id`, if any.
+    pub(crate) fn tag_for(&self, id: std::any::TypeId) -> Option<String> {
+        for page_type in &self.types {
+            let page_type = page_type.read().unwrap();
+            let inner = page_type.inner();
+            let obj = inner.read().unwrap();
+
+            if obj.obj_type.id == id {
+                return Some(obj.obj_type.ident.to_string());
+            }
+        }
+
+        None
     }
 }
 
@@ -24,19 +283,27 @@ impl Config {
 pub struct ConfigBuilder {
     types: Vec<Arc<RwLock<ConcretePageType>>>,
     root: Option<ConcreteRoot>,
+    root_tag: Option<String>,
     offset: usize,
+    mode: ClassificationMode,
+    cache: Option<Arc<dyn Cache>>,
+    thresholds: HashMap<String, ConfidenceThresholds>,
+    default_threshold: ConfidenceThresholds,
+    ensemble_strategy: EnsembleStrategy,
+    candidates: Vec<Candidate<ClassiferError>>,
 }
 
 impl ConfigBuilder {
     pub fn with_obj<T, E>(mut self) -> Self
     where
-        T: Object + Classify + 'static,
+        T: Object + Classify + Send + Sync + 'static,
         E: std::error::Error + Debug + Display + Send + Sync + 'static,
     {
         let mut builder = ConcreteObjectBuilder::new();
         let instanstiated = builder.build::<T, E>();
 
         self.types.push(instanstiated.clone());
+        self.candidates.push(Candidate::<ClassiferError>::of::<T>());
         self
     }
 
@@ -47,6 +314,15 @@ impl ConfigBuilder {
     {
         let root = ConcreteRoot::new();
         self.root = Some(root);
+        // `Root` doesn't require `Object`, so there's no `TypeInformation`
+        // to pull a tag from; fall back to the Rust type name.
+        self.root_tag = Some(
+            std::any::type_name::<T>()
+                .rsplit("::")
+                .next()
+                .unwrap_or("Root")
+                .to_string(),
+        );
 
         self
     }
@@ -59,12 +335,80 @@ impl ConfigBuilder {
         self
     }
 
+    /// Selects which [ClassificationMode] `Classifier::begin`/`classify_chunk`
+    /// run under. Defaults to [ClassificationMode::Full].
+    pub fn set_mode(mut self, mode: ClassificationMode) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    /// Routes classification results through `cache`, e.g.
+    /// [`crate::cache::MemoryCache`] or [`crate::cache::DiskCache`]. See
+    /// [`crate::Classifier::classify_cached`] for how a hit is consulted.
+    pub fn with_cache(mut self, cache: Arc<dyn Cache>) -> Self {
+        self.cache = Some(cache);
+
+        self
+    }
+
+    /// Routes `type_tag` through `thresholds` instead of
+    /// [`ConfigBuilder::set_default_threshold`]'s value.
+    pub fn with_threshold(
+        mut self,
+        type_tag: impl Into<String>,
+        thresholds: ConfidenceThresholds,
+    ) -> Self {
+        self.thresholds.insert(type_tag.into(), thresholds);
+
+        self
+    }
+
+    /// Sets the [`ConfidenceThresholds`] used for any type without its own
+    /// entry from [`ConfigBuilder::with_threshold`]. Defaults to
+    /// [`ConfidenceThresholds::default`].
+    pub fn set_default_threshold(mut self, thresholds: ConfidenceThresholds) -> Self {
+        self.default_threshold = thresholds;
+
+        self
+    }
+
+    /// Selects how [`crate::routing::combine_ensemble`] folds multiple
+    /// `Classify` impls run for the same candidate type. Defaults to
+    /// [`EnsembleStrategy::Max`].
+    pub fn set_ensemble_strategy(mut self, strategy: EnsembleStrategy) -> Self {
+        self.ensemble_strategy = strategy;
+
+        self
+    }
+
     /// Consumes the builder into a Config.
     pub fn build(self) -> Config {
         Config {
             types: self.types,
             root: self.root.expect("A root struct is required!"),
+            root_tag: self.root_tag,
             offset: self.offset,
+            mode: self.mode,
+            cache: self.cache,
+            thresholds: self.thresholds,
+            default_threshold: self.default_threshold,
+            ensemble_strategy: self.ensemble_strategy,
+            candidates: self.candidates,
+        }
+    }
+}
+
+/// Renders a [Pattern] as the kind of one-line description
+/// [ClassificationMode::DryRun] reports, recursing into nested patterns.
+fn describe_pattern(pattern: &Pattern) -> String {
+    match pattern {
+        Pattern::Pair { first, second } => format!("{} <-> {}", first.ident, second.ident),
+        Pattern::Sequence(patterns) => {
+            let steps: Vec<_> = patterns.iter().map(describe_pattern).collect();
+            format!("sequence({})", steps.join(", "))
         }
+        Pattern::Optional(pattern) => format!("optional({})", describe_pattern(pattern)),
+        Pattern::Repetition(pattern) => format!("repeated({})", describe_pattern(pattern)),
     }
 }
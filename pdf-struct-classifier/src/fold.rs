@@ -0,0 +1,172 @@
+//! [`crate::visitor::Visitor`] dispatches every node through one
+//! `visit_object`, so telling a `KeyPage` from an `InferredPage` means
+//! checking `obj_type`/`expected_children` by hand inside the callback.
+//! [`ObjectVisitor`] dispatches off the [`ConcretePageType`] variant
+//! itself instead — `visit_root`/`visit_key`/`visit_inferred`/`visit_pair`,
+//! mirroring rustc's `TypeVisitor`. [`ObjectFolder`] is the read/write
+//! half, mirroring `TypeFoldable`: each hook gets a node whose children
+//! have already been folded, and returns `None` to prune it (and
+//! everything under it) from the tree — renumbering pages, stripping
+//! every `InferredPage` down to a skeleton of `KeyPage`s, or collecting
+//! every `PairWith` pair are each a few lines of `ObjectVisitor`/
+//! `ObjectFolder` rather than a bespoke recursive walk.
+
+use std::sync::{Arc, RwLock};
+
+use crate::instances::{
+    ConcreteInferredPage, ConcreteKeyPage, ConcretePageType, ConcretePair, ConcreteRoot,
+};
+use crate::visitor::ControlFlow;
+
+/// Per-node hooks for a read-only pass over a constructed document,
+/// dispatched off which [`ConcretePageType`] variant a node is. All
+/// default to [`ControlFlow::Continue`]/doing nothing, so implementors
+/// only override what they need.
+pub trait ObjectVisitor {
+    fn visit_root(&mut self, root: &ConcreteRoot) {
+        let _ = root;
+    }
+
+    fn visit_key(&mut self, page: &ConcreteKeyPage, depth: usize) -> ControlFlow {
+        let _ = (page, depth);
+        ControlFlow::Continue
+    }
+
+    fn visit_inferred(&mut self, page: &ConcreteInferredPage, depth: usize) -> ControlFlow {
+        let _ = (page, depth);
+        ControlFlow::Continue
+    }
+
+    /// Called for a `Pair` node in addition to [`ObjectVisitor::visit_inferred`]
+    /// for its underlying page — a pair's own type is always inferred in
+    /// this tree, but only `visit_pair` sees its `sequence`/`patterns`.
+    fn visit_pair(&mut self, pair: &ConcretePair, depth: usize) -> ControlFlow {
+        let _ = (pair, depth);
+        ControlFlow::Continue
+    }
+}
+
+/// Walks `root`, dispatching every reachable node to the matching
+/// [`ObjectVisitor`] hook, depth-first, stopping as soon as one returns
+/// [`ControlFlow::Stop`].
+pub fn walk_typed<V: ObjectVisitor>(root: &ConcreteRoot, visitor: &mut V) -> ControlFlow {
+    visitor.visit_root(root);
+    for child in &root.children {
+        if let ControlFlow::Stop = walk_typed_node(child, 0, visitor) {
+            return ControlFlow::Stop;
+        }
+    }
+    ControlFlow::Continue
+}
+
+fn walk_typed_node<V: ObjectVisitor>(
+    node: &Arc<RwLock<ConcretePageType>>,
+    depth: usize,
+    visitor: &mut V,
+) -> ControlFlow {
+    let flow = match &*node.read().unwrap() {
+        ConcretePageType::Key(page) => visitor.visit_key(page, depth),
+        ConcretePageType::Inferred(page) => visitor.visit_inferred(page, depth),
+        ConcretePageType::Pair(pair) => match visitor.visit_pair(pair, depth) {
+            ControlFlow::Stop => ControlFlow::Stop,
+            ControlFlow::Continue => {
+                let page = ConcreteInferredPage::from(pair.inner.clone());
+                visitor.visit_inferred(&page, depth)
+            }
+        },
+    };
+    if let ControlFlow::Stop = flow {
+        return ControlFlow::Stop;
+    }
+
+    let children = {
+        let inner = node.read().unwrap().inner();
+        let object = inner.read().unwrap();
+        object.children.clone()
+    };
+
+    for child in &children {
+        if let ControlFlow::Stop = walk_typed_node(child, depth + 1, visitor) {
+            return ControlFlow::Stop;
+        }
+    }
+
+    ControlFlow::Continue
+}
+
+/// Per-node hooks for a pass that can replace or drop nodes, threaded
+/// through the `Parent`/`Child` relation — the `ObjectFolder` half of the
+/// rustc-style split, mirroring `TypeFoldable`. Each hook runs after its
+/// node's children have already been folded, and returns `None` to drop
+/// the node (dropping also discards its already-folded children along
+/// with it). Defaults to keeping every node unchanged.
+pub trait ObjectFolder {
+    fn fold_key(&mut self, page: ConcreteKeyPage, depth: usize) -> Option<ConcreteKeyPage> {
+        let _ = depth;
+        Some(page)
+    }
+
+    /// Also applied to the inferred member wrapped by a `Pair` node —
+    /// [`fold_typed`] keeps the pair's own `sequence`/`patterns` intact
+    /// and only swaps in whatever this hook returns for its inner page.
+    fn fold_inferred(
+        &mut self,
+        page: ConcreteInferredPage,
+        depth: usize,
+    ) -> Option<ConcreteInferredPage> {
+        let _ = depth;
+        Some(page)
+    }
+}
+
+/// Folds `root` in place: every node's children are folded before the
+/// node itself is handed to [`ObjectFolder::fold_key`]/
+/// [`ObjectFolder::fold_inferred`], so a folder inspecting
+/// `ConcreteObject::children` always sees the already-folded set.
+pub fn fold_typed<F: ObjectFolder>(root: &mut ConcreteRoot, folder: &mut F) {
+    root.children = std::mem::take(&mut root.children)
+        .into_iter()
+        .filter_map(|child| fold_typed_node(child, 0, folder))
+        .collect();
+}
+
+fn fold_typed_node<F: ObjectFolder>(
+    node: Arc<RwLock<ConcretePageType>>,
+    depth: usize,
+    folder: &mut F,
+) -> Option<Arc<RwLock<ConcretePageType>>> {
+    let inner = node.read().unwrap().inner();
+
+    let children = std::mem::take(&mut inner.write().unwrap().children);
+    let folded_children: Vec<_> = children
+        .into_iter()
+        .filter_map(|child| fold_typed_node(child, depth + 1, folder))
+        .collect();
+    inner.write().unwrap().children = folded_children;
+
+    let page_type = node.read().unwrap().clone();
+    let folded = match page_type {
+        ConcretePageType::Key(page) => folder.fold_key(page, depth).map(ConcretePageType::Key),
+        ConcretePageType::Inferred(page) => folder
+            .fold_inferred(page, depth)
+            .map(ConcretePageType::Inferred),
+        ConcretePageType::Pair(mut pair) => {
+            let page = ConcreteInferredPage::from(pair.inner.clone());
+            folder.fold_inferred(page, depth).map(|folded| {
+                pair.inner = folded.inner();
+                ConcretePageType::Pair(pair)
+            })
+        }
+    };
+
+    // Mutate `node`'s contents in place and hand back the same `Arc`,
+    // rather than allocating a new outer `Arc` for the folded result —
+    // kept children's `ConcreteObject::parent` already points at this
+    // `Arc`, and `navigation.rs`'s sibling/ancestor lookups locate a node
+    // in its parent's children by `Arc::ptr_eq`, so a fresh `Arc` here
+    // would silently detach the folded tree from both.
+    folded.map(|page_type| {
+        *node.write().unwrap() = page_type;
+        node
+    })
+}
@@ -0,0 +1,62 @@
+//! Incremental re-classification: reuse a prior [`DocumentStructure`] sidecar
+//! instead of re-running OCR over pages whose bytes haven't changed.
+//!
+//! The critical invariant enforced here is that a page is only reused when
+//! **both** of the following hold:
+//!   - its own content fingerprint matches the prior run's fingerprint, and
+//!   - every task it transitively depends on (via [`crate::scheduler::Pass::depends_on`])
+//!     was itself reused rather than recomputed.
+//!
+//! A page failing either check — and anything depending on it — falls back
+//! to a full classify.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use crate::sidecar::DocumentStructure;
+
+/// A cheap, stable-within-a-run content fingerprint of a page's raw bytes.
+pub fn fingerprint(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Tracks, across one classification run, which tasks were reused verbatim
+/// from a prior sidecar and which had to be recomputed — surfaced to
+/// callers as a report of work actually done.
+#[derive(Default)]
+pub struct IncrementalReport {
+    pub reused: Vec<(i32, String)>,
+    pub recomputed: Vec<(i32, String)>,
+}
+
+impl IncrementalReport {
+    pub fn record_reused(&mut self, page: i32, type_tag: String) {
+        self.reused.push((page, type_tag));
+    }
+
+    pub fn record_recomputed(&mut self, page: i32, type_tag: String) {
+        self.recomputed.push((page, type_tag));
+    }
+}
+
+/// Decides whether `(page, type_tag)` can be reused from `prior`, given that
+/// `dependencies_reused` reflects whether every task it depends on was
+/// itself reused this run.
+pub fn can_reuse(
+    prior: &DocumentStructure,
+    page: i32,
+    type_tag: &str,
+    current_fingerprint: u64,
+    dependencies_reused: bool,
+) -> bool {
+    if !dependencies_reused {
+        return false;
+    }
+
+    match prior.record_for(page, type_tag) {
+        Some(record) => record.fingerprint == Some(current_fingerprint),
+        None => false,
+    }
+}
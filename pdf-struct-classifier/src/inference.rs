@@ -0,0 +1,197 @@
+//! Turns `ConcretePageType::Inferred` from a marker into a capability: a
+//! worklist-based, expectation-driven resolution pass that starts from
+//! `Key` nodes (known by construction — a `KeyPage` must already have been
+//! explicitly classified) and propagates `expected_children`/pattern
+//! obligations out to their `Inferred` neighbours, pruning each neighbour's
+//! [`Expectation`] as scores come in until it diverges to one candidate or
+//! is left ambiguous.
+//!
+//! Actually invoking a candidate's casted classification method requires
+//! the caller's concrete `T`/`E` at the call site (see
+//! [`crate::instances::ConcreteObject::cast_classification`]) — the same
+//! gap [`crate::reclassify`]'s actor walk documents — so this engine is
+//! driven by [`CandidateScore`]s the caller has already produced, rather
+//! than running classification itself.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use pdf_struct_traits::{ConfidenceScore, TypeInformation};
+use std::any::TypeId;
+
+/// Identifies one physical tree slot awaiting resolution. Opaque to this
+/// module — callers mint these however they already track pages (a cache
+/// key, an index, a pointer's identity).
+pub type PageHandle = u64;
+
+/// The candidate types a [`PageHandle`] could still resolve to: seeded
+/// from its parent's `expected_children` (plus any `ConcretePair`
+/// pattern/sequence obligations the caller folds in) and pruned as scores
+/// arrive. `diverged` flips once the set has been pruned to exactly one
+/// candidate.
+#[derive(Clone, Debug, Default)]
+pub struct Expectation {
+    pub candidates: HashSet<TypeId>,
+    pub diverged: bool,
+}
+
+impl Expectation {
+    pub fn new(candidates: impl IntoIterator<Item = TypeInformation>) -> Self {
+        Self {
+            candidates: candidates.into_iter().map(|ty| ty.id).collect(),
+            diverged: false,
+        }
+    }
+
+    fn retain(&mut self, allowed: &HashSet<TypeId>) {
+        self.candidates.retain(|id| allowed.contains(id));
+        self.diverged = self.candidates.len() == 1;
+    }
+}
+
+/// One classification attempt a caller has already run for `handle`
+/// against one of the candidates in its [`Expectation`].
+pub struct CandidateScore {
+    pub handle: PageHandle,
+    pub candidate: TypeId,
+    pub score: ConfidenceScore,
+}
+
+/// The outcome of [`Engine::run`]: pages the worklist pruned to a single
+/// candidate, and pages left with more than one (or none).
+pub struct Resolution {
+    pub resolved: HashMap<PageHandle, TypeId>,
+    pub unresolved: Vec<PageHandle>,
+}
+
+/// Worklist-based constraint propagation over a tree of [`PageHandle`]s.
+/// Seed every page with [`Engine::seed`] (or [`Engine::seed_known`] for a
+/// `Key` page whose type isn't in question), feed in [`CandidateScore`]s
+/// as they're computed, then call [`Engine::run`].
+#[derive(Default)]
+pub struct Engine {
+    expectations: HashMap<PageHandle, Expectation>,
+    scores: HashMap<PageHandle, Vec<CandidateScore>>,
+    parent_of: HashMap<PageHandle, PageHandle>,
+    children_of: HashMap<PageHandle, Vec<PageHandle>>,
+}
+
+impl Engine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seeds `handle` with its starting candidate set. `parent`, if given,
+    /// records the edge [`Engine::run`] walks: resolving `handle` re-queues its
+    /// siblings, and siblings resolving re-queues `handle`.
+    pub fn seed(
+        &mut self,
+        handle: PageHandle,
+        expectation: Expectation,
+        parent: Option<PageHandle>,
+    ) {
+        self.expectations.insert(handle, expectation);
+        if let Some(parent) = parent {
+            self.parent_of.insert(handle, parent);
+            self.children_of.entry(parent).or_default().push(handle);
+        }
+    }
+
+    /// Seeds `handle` as already known — a `Key` page, explicitly
+    /// classified rather than inferred — so [`Engine::run`] treats it as
+    /// resolved from the start and propagates it to its siblings.
+    pub fn seed_known(
+        &mut self,
+        handle: PageHandle,
+        resolved_type: TypeId,
+        parent: Option<PageHandle>,
+    ) {
+        self.seed(
+            handle,
+            Expectation {
+                candidates: HashSet::from([resolved_type]),
+                diverged: true,
+            },
+            parent,
+        );
+        self.scores.entry(handle).or_default().push(CandidateScore {
+            handle,
+            candidate: resolved_type,
+            score: ConfidenceScore::MAX,
+        });
+    }
+
+    /// Records a classification attempt for one of `handle`'s candidates.
+    pub fn record_score(&mut self, score: CandidateScore) {
+        self.scores.entry(score.handle).or_default().push(score);
+    }
+
+    /// Runs the worklist to a fixed point. A handle's candidate set is
+    /// pruned to whichever of its recorded scores cleared `threshold`;
+    /// once that prunes it down to exactly one candidate (it "diverges"),
+    /// that candidate is removed from every sibling sharing its parent —
+    /// they can't also be the unique resolved type — and the siblings are
+    /// re-queued so their own divergence can cascade in turn.
+    pub fn run(mut self, threshold: ConfidenceScore) -> Resolution {
+        let mut worklist: VecDeque<PageHandle> = self.scores.keys().copied().collect();
+        let mut resolved: HashMap<PageHandle, TypeId> = HashMap::new();
+
+        while let Some(handle) = worklist.pop_front() {
+            if resolved.contains_key(&handle) {
+                continue;
+            }
+
+            let Some(expectation) = self.expectations.get_mut(&handle) else {
+                continue;
+            };
+
+            if let Some(attempts) = self.scores.get(&handle) {
+                let passing: HashSet<TypeId> = attempts
+                    .iter()
+                    .filter(|attempt| attempt.score >= threshold)
+                    .map(|attempt| attempt.candidate)
+                    .collect();
+                if !passing.is_empty() {
+                    expectation.retain(&passing);
+                }
+            }
+
+            if !expectation.diverged {
+                continue;
+            }
+
+            let winner = *expectation.candidates.iter().next().unwrap();
+            resolved.insert(handle, winner);
+
+            let Some(parent) = self.parent_of.get(&handle).copied() else {
+                continue;
+            };
+            let Some(siblings) = self.children_of.get(&parent) else {
+                continue;
+            };
+
+            for sibling in siblings.clone() {
+                if sibling == handle {
+                    continue;
+                }
+                if let Some(sibling_expectation) = self.expectations.get_mut(&sibling) {
+                    if sibling_expectation.candidates.remove(&winner) {
+                        sibling_expectation.diverged = sibling_expectation.candidates.len() == 1;
+                        worklist.push_back(sibling);
+                    }
+                }
+            }
+        }
+
+        let unresolved = self
+            .expectations
+            .keys()
+            .filter(|handle| !resolved.contains_key(handle))
+            .copied()
+            .collect();
+
+        Resolution {
+            resolved,
+            unresolved,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use dashmap::DashMap;
 use pdf_struct_traits::*;
+
+use crate::coercion::CoercionTable;
 use std::any::{Any, TypeId};
+use std::collections::HashMap;
 use std::error::Error;
 use std::fmt::{Debug, Display};
 use std::sync::{Arc, RwLock, Weak};
@@ -38,13 +41,18 @@ impl ConcretePageType {
     }
 }
 
-pub(crate) trait AnyClone: Any {
+/// `Send + Sync` so a `Box<dyn AnyClone>` (stored in
+/// [`ConcreteObject::classification_method`]/[`ConcreteObject::extraction_method`])
+/// can travel inside a [`ConcreteRoot`] onto [`crate::reclassify::RootHandle`]'s
+/// background thread — every value actually erased into one today is a bare
+/// `fn` pointer, which is always `Send + Sync`.
+pub(crate) trait AnyClone: Any + Send + Sync {
     /// Deep-clones the underlying data within a Box
     /// Or in other words, clones T within Box<T>.
     fn clone_box(&self) -> Box<dyn AnyClone>;
 }
 
-impl<T: Any + Clone> AnyClone for T {
+impl<T: Any + Clone + Send + Sync> AnyClone for T {
     fn clone_box(&self) -> Box<dyn AnyClone> {
         Box::new(self.clone())
     }
@@ -61,14 +69,415 @@ pub(crate) type ClassificationMethod<T, E> = fn(&[u8]) -> ClassificationResult<T
 ///     S is Self (the constructed object)
 pub(crate) type ExtractionMethod<T, E, S> = fn(&[u8], T) -> Result<S, E>;
 
-/// Cache holding information of each  
+/// Cache holding information of each
 pub(crate) type ObjectCache = DashMap<TypeId, Arc<RwLock<ConcretePageType>>>;
 
-/// Crate-level error that can only be called when attempting
-/// to cast into a [ClassificationMethod] or [ExtractionMethod]
-#[derive(Debug)]
-pub(crate) enum CastError {
-    TypeMismatch { expected: TypeId, actual: TypeId },
+/// A cloned, lock-free view of one cached object: its type ident and its
+/// parent's type id (if any); its own type id is the snapshot map's key.
+/// The type ids it's allowed to parent are not recorded here. Built once per
+/// [`ObjectCache`] by [`snapshot_cache`].
+struct ObjectSnapshot {
+    ident: String,
+    parent: Option<TypeId>,
+}
+
+/// Takes a single-pass snapshot of `cache`: for each entry, one read lock on
+/// its `ConcretePageType` then one on its `ConcreteObject` — dropped before
+/// the next entry or its parent is ever touched, so no two locks are held at
+/// once. Parent is resolved to a [`TypeId`] in a second, lock-free pass over
+/// pointers collected during the first, rather than locking a second entry
+/// while the first is still held. Replaces the `try_read`/sleep/retry
+/// scanning [`ConcreteObject::collect_children_from_cache`] and
+/// [`ConcreteRoot::connect_relationships`] used to do.
+fn snapshot_cache(cache: &ObjectCache) -> HashMap<TypeId, ObjectSnapshot> {
+    struct Raw {
+        id: TypeId,
+        ident: String,
+        self_ptr: *const RwLock<ConcreteObject>,
+        parent_ptr: Option<*const RwLock<ConcreteObject>>,
+    }
+
+    let raw: Vec<Raw> = cache
+        .iter()
+        .map(|item| {
+            let inner = item.value().read().unwrap().inner();
+            let self_ptr = Arc::as_ptr(&inner);
+
+            let obj = inner.read().unwrap();
+            let id = obj.obj_type.id;
+            let ident = obj.obj_type.ident.to_string();
+            let parent_page_type = obj.parent.clone();
+            drop(obj);
+
+            let parent_ptr =
+                parent_page_type.map(|parent| Arc::as_ptr(&parent.read().unwrap().inner()));
+
+            Raw {
+                id,
+                ident,
+                self_ptr,
+                parent_ptr,
+            }
+        })
+        .collect();
+
+    let ptr_to_id: HashMap<*const RwLock<ConcreteObject>, TypeId> =
+        raw.iter().map(|entry| (entry.self_ptr, entry.id)).collect();
+
+    raw.into_iter()
+        .map(|entry| {
+            let parent = entry
+                .parent_ptr
+                .and_then(|ptr| ptr_to_id.get(&ptr).copied());
+            (
+                entry.id,
+                ObjectSnapshot {
+                    ident: entry.ident,
+                    parent,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Union-find over [`TypeId`] with path compression, used to validate that
+/// an [`ObjectCache`]'s parent chain is acyclic in near-linear amortized
+/// time instead of walking each chain by hand.
+struct UnionFind {
+    parent: HashMap<TypeId, TypeId>,
+}
+
+impl UnionFind {
+    fn find(&mut self, x: TypeId) -> TypeId {
+        let p = *self.parent.entry(x).or_insert(x);
+        if p == x {
+            return x;
+        }
+        let root = self.find(p);
+        self.parent.insert(x, root);
+        root
+    }
+
+    /// Unions `child` and `parent`, returning `false` if they were already
+    /// in the same set — meaning this parent edge would close a cycle.
+    fn union(&mut self, child: TypeId, parent: TypeId) -> bool {
+        let child_root = self.find(child);
+        let parent_root = self.find(parent);
+        if child_root == parent_root {
+            return false;
+        }
+        self.parent.insert(child_root, parent_root);
+        true
+    }
+}
+
+/// Resolves every parent -> children edge set across `cache` in one
+/// deterministic pass: snapshots it with [`snapshot_cache`], validates the
+/// parent chain is acyclic with a [`UnionFind`] keyed by `TypeId` (rejecting
+/// a cycle with an error instead of looping forever), and returns each
+/// parent's children sorted by `TypeInformation::ident` so the result is
+/// reproducible across runs.
+fn resolve_children(cache: &ObjectCache) -> Result<HashMap<TypeId, Vec<TypeId>>, String> {
+    let snapshot = snapshot_cache(cache);
+
+    let mut union_find = UnionFind {
+        parent: HashMap::new(),
+    };
+    for (&id, entry) in &snapshot {
+        if let Some(parent_id) = entry.parent {
+            if !union_find.union(id, parent_id) {
+                return Err(format!(
+                    "Cycle detected in parent chain at type {}",
+                    entry.ident
+                ));
+            }
+        }
+    }
+
+    let mut children: HashMap<TypeId, Vec<TypeId>> = HashMap::new();
+    for (&id, entry) in &snapshot {
+        if let Some(parent_id) = entry.parent {
+            children.entry(parent_id).or_default().push(id);
+        }
+    }
+
+    for child_ids in children.values_mut() {
+        child_ids.sort_by(|a, b| snapshot[a].ident.cmp(&snapshot[b].ident));
+    }
+
+    Ok(children)
+}
+
+/// Describes the concrete function-pointer type type-erased into a
+/// [ConcreteObject::classification_method]/[extraction_method](ConcreteObject::extraction_method),
+/// captured once at construction time (see [ConcreteObject::from_obj_internal])
+/// so a cast mismatch can name what's actually stored instead of only
+/// comparing opaque [TypeId]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CastSignature {
+    pub type_id: TypeId,
+    pub ident: &'static str,
+}
+
+impl CastSignature {
+    pub(crate) fn of<F: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<F>(),
+            ident: std::any::type_name::<F>(),
+        }
+    }
+}
+
+impl std::fmt::Display for CastSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.ident)
+    }
+}
+
+/// Error surfaced by the safe cast layer around
+/// [ConcreteObject::classification_method]/[extraction_method](ConcreteObject::extraction_method),
+/// and by [ConcreteKeyPage::new]/[ConcreteInferredPage::new] when a type's
+/// `KEY_PAGE`/`INFERRED_PAGE` flag disagrees with the constructor asked
+/// for. `pub` since both constructors surface it in their public `Result`.
+#[derive(thiserror::Error, Debug)]
+pub enum CastError {
+    /// The `T`/`E` a caller requested don't match what the object was
+    /// actually constructed with.
+    #[error("expected classification for `{actual}`, got request for `{expected}`")]
+    TypeMismatch {
+        expected: CastSignature,
+        actual: CastSignature,
+        /// Captured only when built with `--features backtrace`; cheap to
+        /// carry otherwise since it's simply absent.
+        #[cfg(feature = "backtrace")]
+        backtrace: std::backtrace::Backtrace,
+    },
+    #[error("cannot construct `{ident}` as a key page: its `KEY_PAGE` flag is false")]
+    NotKeyPage { ident: &'static str },
+    #[error("cannot construct `{ident}` as an inferred page: its `INFERRED_PAGE` flag is false")]
+    NotInferredPage { ident: &'static str },
+}
+
+impl CastError {
+    fn type_mismatch(expected: CastSignature, actual: CastSignature) -> Self {
+        CastError::TypeMismatch {
+            expected,
+            actual,
+            #[cfg(feature = "backtrace")]
+            backtrace: std::backtrace::Backtrace::capture(),
+        }
+    }
+}
+
+/// One candidate considered by [`resolve_candidates`] for an ambiguous
+/// slot: what it scored (`None` if it errored outright). An `Uncertain`
+/// candidate keeps its score here too; which tier a candidate landed in is
+/// not recorded. Recorded verbatim on the
+/// winning [`ConcreteObject::candidate_ranking`] so a caller can see what
+/// else was in the running.
+#[derive(Clone, Debug)]
+pub struct RankedCandidate {
+    pub type_info: TypeInformation,
+    pub confidence: Option<ConfidenceScore>,
+}
+
+/// How [`resolve_candidates`] picks a winner among candidates that scored
+/// `Confident`/`Probable`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DisambiguationPolicy {
+    /// Take the highest-scoring candidate, however close the runner-up is.
+    HighestConfidence,
+    /// Take the highest-scoring candidate, but refuse (returning
+    /// [`DisambiguationError::Ambiguous`]) if the runner-up is within this
+    /// many points of it.
+    MarginThreshold(ConfidenceScore),
+}
+
+/// Raised by [`resolve_candidates`].
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum DisambiguationError<E: Error + Debug + Display + 'static> {
+    #[error("no candidate classified the bytes confidently enough to extract")]
+    NoConfidentCandidate,
+    #[error("`{top}` ({top_score:.1}) and `{runner_up}` ({runner_up_score:.1}) are within {margin:.1} points of each other")]
+    Ambiguous {
+        top: &'static str,
+        runner_up: &'static str,
+        top_score: ConfidenceScore,
+        runner_up_score: ConfidenceScore,
+        margin: ConfidenceScore,
+    },
+    #[error("winning candidate `{ident}`'s extraction failed: {source}")]
+    Extraction { ident: &'static str, source: E },
+}
+
+/// [`ClassificationMethod`]/[`ExtractionMethod`] with `SharedData` erased
+/// to `Box<dyn Any + Send + Sync>`, so [`resolve_candidates`] can hold
+/// several candidates of differing `SharedData`/`Self` types in one `Vec`.
+pub(crate) type ErasedClassify<E> =
+    fn(&[u8]) -> ClassificationResult<Box<dyn Any + Send + Sync>, E>;
+pub(crate) type ErasedExtract<E> =
+    fn(&[u8], Box<dyn Any + Send + Sync>) -> Result<Box<dyn Any + Send + Sync>, E>;
+
+fn classify_erased<T, E>(bytes: &[u8]) -> ClassificationResult<Box<dyn Any + Send + Sync>, E>
+where
+    T: Object + 'static,
+    E: Error + Debug + Display + 'static,
+{
+    match T::classify::<E>(bytes) {
+        ClassificationResult::Confident(score, data) => {
+            ClassificationResult::Confident(score, Box::new(data) as Box<dyn Any + Send + Sync>)
+        }
+        ClassificationResult::Probable(score, data) => {
+            ClassificationResult::Probable(score, Box::new(data) as Box<dyn Any + Send + Sync>)
+        }
+        ClassificationResult::Uncertain(score) => ClassificationResult::Uncertain(score),
+        ClassificationResult::Err(e) => ClassificationResult::Err(e),
+    }
+}
+
+fn extract_erased<T, E>(
+    bytes: &[u8],
+    shared: Box<dyn Any + Send + Sync>,
+) -> Result<Box<dyn Any + Send + Sync>, E>
+where
+    T: Object + Send + Sync + 'static,
+    E: Error + Debug + Display + 'static,
+{
+    let shared = *shared
+        .downcast::<T::SharedData>()
+        .expect("resolve_candidates only ever threads a candidate's own SharedData back into it");
+    T::extract::<E>(bytes, shared).map(|obj| Box::new(obj) as Box<dyn Any + Send + Sync>)
+}
+
+/// A single registered candidate `Object` type for an ambiguous slot,
+/// type-erased so [`resolve_candidates`] can hold a mixed `Vec` of them.
+/// Build one per candidate with [`Candidate::of`].
+pub(crate) struct Candidate<E: Error + Debug + Display + 'static> {
+    type_info: TypeInformation,
+    children: &'static [TypeInformation],
+    classify: ErasedClassify<E>,
+    extract: ErasedExtract<E>,
+}
+
+impl<E: Error + Debug + Display + 'static> Candidate<E> {
+    pub(crate) fn of<T>() -> Self
+    where
+        T: Object + Send + Sync + 'static,
+    {
+        Self {
+            type_info: T::TYPE,
+            children: T::CHILDREN,
+            classify: classify_erased::<T, E>,
+            extract: extract_erased::<T, E>,
+        }
+    }
+
+    pub(crate) fn type_info(&self) -> &TypeInformation {
+        &self.type_info
+    }
+
+    /// Runs this candidate's erased [`Classify::classify`], without
+    /// touching [`Candidate::extract`] — callers that only need a
+    /// confidence score (e.g. [`crate::Classifier::classify_chunk`]'s
+    /// `Check`/`Full` dispatch before a winner is chosen) can skip
+    /// extraction entirely.
+    pub(crate) fn run_classify(
+        &self,
+        bytes: &[u8],
+    ) -> ClassificationResult<Box<dyn Any + Send + Sync>, E> {
+        (self.classify)(bytes)
+    }
+}
+
+/// Classifies `bytes` against every one of `candidates`, ranks the ones
+/// that came back `Confident`/`Probable` by score, and selects a winner per
+/// `policy`. Instead of reclassifying, the winner's own `SharedData` is
+/// threaded straight into its `extract` — so the constructed value reflects
+/// exactly what won, not a second attempt — and the full ranked candidate
+/// list (including ones that errored or came back `Uncertain`) is recorded
+/// on the returned [`ConcreteObject::candidate_ranking`] for later
+/// inspection.
+pub(crate) fn resolve_candidates<E>(
+    bytes: &[u8],
+    candidates: &[Candidate<E>],
+    policy: DisambiguationPolicy,
+) -> Result<(ConcreteObject, Box<dyn Any + Send + Sync>), DisambiguationError<E>>
+where
+    E: Error + Debug + Display + 'static,
+{
+    let mut ranking: Vec<RankedCandidate> = Vec::with_capacity(candidates.len());
+    let mut scored: Vec<(usize, ConfidenceScore, Box<dyn Any + Send + Sync>)> = Vec::new();
+
+    for (index, candidate) in candidates.iter().enumerate() {
+        match (candidate.classify)(bytes) {
+            ClassificationResult::Confident(score, data)
+            | ClassificationResult::Probable(score, data) => {
+                ranking.push(RankedCandidate {
+                    type_info: candidate.type_info.clone(),
+                    confidence: Some(score),
+                });
+                scored.push((index, score, data));
+            }
+            ClassificationResult::Uncertain(score) => {
+                ranking.push(RankedCandidate {
+                    type_info: candidate.type_info.clone(),
+                    confidence: Some(score),
+                });
+            }
+            ClassificationResult::Err(_) => {
+                ranking.push(RankedCandidate {
+                    type_info: candidate.type_info.clone(),
+                    confidence: None,
+                });
+            }
+        }
+    }
+
+    if scored.is_empty() {
+        return Err(DisambiguationError::NoConfidentCandidate);
+    }
+
+    scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+    if let DisambiguationPolicy::MarginThreshold(margin) = policy {
+        if scored.len() > 1 {
+            let top_score = scored[0].1;
+            let runner_up_index = scored[1].0;
+            let runner_up_score = scored[1].1;
+            if top_score - runner_up_score < margin {
+                return Err(DisambiguationError::Ambiguous {
+                    top: candidates[scored[0].0].type_info.ident,
+                    runner_up: candidates[runner_up_index].type_info.ident,
+                    top_score,
+                    runner_up_score,
+                    margin,
+                });
+            }
+        }
+    }
+
+    let (winner_index, _, winner_data) = scored.remove(0);
+    let winner = &candidates[winner_index];
+
+    let extracted =
+        (winner.extract)(bytes, winner_data).map_err(|source| DisambiguationError::Extraction {
+            ident: winner.type_info.ident,
+            source,
+        })?;
+
+    let object = ConcreteObject {
+        parent: None,
+        children: vec![],
+        classification_method: Box::new(winner.classify) as Box<dyn AnyClone>,
+        extraction_method: Box::new(winner.extract) as Box<dyn AnyClone>,
+        classification_signature: CastSignature::of::<ErasedClassify<E>>(),
+        extraction_signature: CastSignature::of::<ErasedExtract<E>>(),
+        obj_type: winner.type_info.clone(),
+        expected_children: winner.children.to_vec(),
+        candidate_ranking: ranking,
+    };
+
+    Ok((object, extracted))
 }
 
 /// Represents a type that implements [pdf_struct_traits::Object] at runtime.
@@ -87,11 +496,24 @@ pub struct ConcreteObject {
     /// ! This member should NOT be manually set or casted into.
     /// ! Utilize [ConcreteObject::cast_extraction]
     pub(crate) extraction_method: Box<dyn AnyClone>,
+    /// The function-pointer type erased into [Self::classification_method],
+    /// captured at construction so [ConcreteObject::cast_classification]
+    /// can name it in a [CastError::TypeMismatch] without re-deriving it.
+    pub(crate) classification_signature: CastSignature,
+    /// The function-pointer type erased into [Self::extraction_method],
+    /// captured at construction so [ConcreteObject::cast_extraction] can
+    /// name it in a [CastError::TypeMismatch] without re-deriving it.
+    pub(crate) extraction_signature: CastSignature,
     /// Reflected information of the type defined as an object
     /// Which Self represents at runtime.
     pub(crate) obj_type: TypeInformation,
     /// The reflected type information for the children of this type.
     pub(crate) expected_children: Vec<TypeInformation>,
+    /// The full ranked candidate list [`resolve_candidates`] considered
+    /// before settling on this object's type, if it was built that way.
+    /// Empty for every object built through the ordinary single-type
+    /// [`ConcreteObject::from_obj_with_cache`] path.
+    pub(crate) candidate_ranking: Vec<RankedCandidate>,
 }
 
 impl ConcreteObject {
@@ -124,18 +546,24 @@ impl ConcreteObject {
         page_type
     }
 
-    /// Casts T and E into fn<T, E>(&\[u8]) -> ClassificationResult<T, E>;
-    /// This method is unsafe because [ConcreteObject::classification_method]
-    /// may not match the expected TypeId to cast back into a concrete [ClassificationMethod]
-    pub(crate) unsafe fn cast_classification<T, E>(
-        &self,
-    ) -> Result<ClassificationMethod<T, E>, CastError>
+    /// Safely casts into `fn(&[u8]) -> ClassificationResult<T, E>`: checks
+    /// the requested `T`/`E` against the signature [Self::classification_signature]
+    /// recorded at construction before ever touching the type-erased
+    /// [Self::classification_method], so a wrong instantiation yields a
+    /// descriptive [CastError::TypeMismatch] instead of a failed downcast.
+    pub(crate) fn cast_classification<T, E>(&self) -> Result<ClassificationMethod<T, E>, CastError>
     where
         T: Send + Sync + 'static,
         E: Error + Debug + Display + 'static,
     {
-        let expected_type_id = TypeId::of::<fn(&[u8]) -> ClassificationResult<T, E>>();
-        let actual_type_id = self.classification_method.type_id();
+        let expected = CastSignature::of::<fn(&[u8]) -> ClassificationResult<T, E>>();
+
+        if expected.type_id != self.classification_signature.type_id {
+            return Err(CastError::type_mismatch(
+                expected,
+                self.classification_signature,
+            ));
+        }
 
         let func_ptr =
             (self.classification_method.as_ref() as &dyn Any).downcast_ref::<fn(
@@ -144,39 +572,37 @@ impl ConcreteObject {
                 -> ClassificationResult<T, E>>(
             );
 
-        match func_ptr {
-            Some(f) => Ok(*f),
-            None => Err(CastError::TypeMismatch {
-                expected: expected_type_id,
-                actual: actual_type_id,
-            }),
-        }
+        func_ptr
+            .copied()
+            .ok_or_else(|| CastError::type_mismatch(expected, self.classification_signature))
     }
 
-    /// Casts T, E, S into fn(&\[u8], T) -> Result<S, E>;
-    /// This method is unsafe because [ConcreteObject::extraction_method]
-    /// may not match the expected TypeId to cast back into a concrete [ExtractionMethod]
-    pub(crate) unsafe fn cast_extraction<T, E, S>(
-        &self,
-    ) -> Result<ExtractionMethod<T, E, S>, CastError>
+    /// Safely casts into `fn(&[u8], T) -> Result<S, E>`: checks the
+    /// requested `T`/`E`/`S` against the signature [Self::extraction_signature]
+    /// recorded at construction before ever touching the type-erased
+    /// [Self::extraction_method], so a wrong instantiation yields a
+    /// descriptive [CastError::TypeMismatch] instead of a failed downcast.
+    pub(crate) fn cast_extraction<T, E, S>(&self) -> Result<ExtractionMethod<T, E, S>, CastError>
     where
         T: Send + Sync + 'static,
         E: Error + Debug + Display + 'static,
         S: Sized + 'static,
     {
-        let expected_type_id = TypeId::of::<fn(&[u8], T) -> Result<S, E>>();
-        let actual_type_id = self.extraction_method.type_id();
+        let expected = CastSignature::of::<fn(&[u8], T) -> Result<S, E>>();
+
+        if expected.type_id != self.extraction_signature.type_id {
+            return Err(CastError::type_mismatch(
+                expected,
+                self.extraction_signature,
+            ));
+        }
 
         let func_ptr = (self.extraction_method.as_ref() as &dyn Any)
             .downcast_ref::<fn(&[u8], T) -> Result<S, E>>();
 
-        match func_ptr {
-            Some(f) => Ok(*f),
-            None => Err(CastError::TypeMismatch {
-                expected: expected_type_id,
-                actual: actual_type_id,
-            }),
-        }
+        func_ptr
+            .copied()
+            .ok_or_else(|| CastError::type_mismatch(expected, self.extraction_signature))
     }
 
     /// Internal method that does the actual construction
@@ -200,8 +626,15 @@ impl ConcreteObject {
             extraction_method: Box::new(
                 T::extract::<E> as ExtractionMethod<<T as Classify>::SharedData, E, T>,
             ),
+            classification_signature: CastSignature::of::<
+                ClassificationMethod<<T as Classify>::SharedData, E>,
+            >(),
+            extraction_signature: CastSignature::of::<
+                ExtractionMethod<<T as Classify>::SharedData, E, T>,
+            >(),
             obj_type: T::TYPE,
             expected_children: T::CHILDREN.to_vec(),
+            candidate_ranking: vec![],
         }
     }
 
@@ -258,91 +691,53 @@ impl ConcreteObject {
         self.children.push(child);
     }
 
-    /// Find and add all children from cache that have this object as their parent
-    pub fn collect_children_from_cache(&mut self, cache: &ObjectCache) {
-        let mut existing_child_types: Vec<TypeId> = Vec::new();
-        for child in &self.children {
-            if let Ok(child_inner) = child.try_read() {
-                if let Ok(child_obj) = child_inner.inner().try_read() {
-                    existing_child_types.push(child_obj.obj_type.id);
-                }
-            }
-        }
-
-        let expected_child_types: Vec<TypeId> = self
-            .expected_children
+    /// Find and add all children from `cache` that have this object as
+    /// their parent, skipping any already present. Resolves the whole
+    /// cache's parent/child edges in one deterministic pass via
+    /// [`resolve_children`] instead of the `try_read`/sleep/retry scanning
+    /// this used to do, and rejects a cyclic parent chain with an error
+    /// rather than looping forever.
+    pub fn collect_children_from_cache(&mut self, cache: &ObjectCache) -> Result<(), String> {
+        let children_by_parent = resolve_children(cache)?;
+
+        let mut existing_child_types: Vec<TypeId> = self
+            .children
             .iter()
-            .map(|type_info| type_info.id)
+            .map(|child| child.read().unwrap().inner().read().unwrap().obj_type.id)
             .collect();
 
-        let mut candidates: Vec<(Arc<RwLock<ConcretePageType>>, TypeId, String)> = Vec::new();
+        let Some(child_ids) = children_by_parent.get(&self.obj_type.id) else {
+            return Ok(());
+        };
 
+        let mut by_id: HashMap<TypeId, Arc<RwLock<ConcretePageType>>> = HashMap::new();
         for item in cache.iter() {
-            let obj = item.value();
+            let id = item
+                .value()
+                .read()
+                .unwrap()
+                .inner()
+                .read()
+                .unwrap()
+                .obj_type
+                .id;
+            by_id.insert(id, item.value().clone());
+        }
 
-            if self.children.iter().any(|child| Arc::ptr_eq(child, obj)) {
+        for child_id in child_ids {
+            if existing_child_types.contains(child_id) {
                 continue;
             }
-
-            let type_info = {
-                let max_attempts = 3;
-                let mut attempt_count = 0;
-
-                loop {
-                    if let Ok(obj_guard) = obj.try_read() {
-                        let result = match &*obj_guard {
-                            ConcretePageType::Key(key_page) => {
-                                if let Ok(inner) = key_page.inner().try_read() {
-                                    Some((inner.obj_type.id, inner.obj_type.ident.to_string()))
-                                } else {
-                                    None
-                                }
-                            }
-                            ConcretePageType::Inferred(inferred_page) => {
-                                if let Ok(inner) = inferred_page.inner().try_read() {
-                                    Some((inner.obj_type.id, inner.obj_type.ident.to_string()))
-                                } else {
-                                    None
-                                }
-                            }
-                            ConcretePageType::Pair(pair) => {
-                                if let Ok(inner) = pair.inner.try_read() {
-                                    Some((inner.obj_type.id, inner.obj_type.ident.to_string()))
-                                } else {
-                                    None
-                                }
-                            }
-                        };
-
-                        drop(obj_guard);
-
-                        if let Some((type_id, type_name)) = result {
-                            break Some((type_id, type_name));
-                        }
-                    }
-
-                    attempt_count += 1;
-                    if attempt_count >= max_attempts {
-                        break None;
-                    }
-
-                    std::thread::sleep(std::time::Duration::from_millis(1));
-                }
-            };
-
-            if let Some((type_id, type_name)) = type_info {
-                if !existing_child_types.contains(&type_id) {
-                    if expected_child_types.contains(&type_id) {
-                        candidates.push((obj.clone(), type_id, type_name));
-                    }
-                }
+            if !self.expected_children.iter().any(|ty| ty.id == *child_id) {
+                continue;
+            }
+            if let Some(child) = by_id.get(child_id) {
+                self.children.push(child.clone());
+                existing_child_types.push(*child_id);
             }
         }
 
-        for (obj, type_id, _type_name) in candidates {
-            self.children.push(obj);
-            existing_child_types.push(type_id);
-        }
+        Ok(())
     }
 
     /// Check if this object can have a specific child type
@@ -372,8 +767,11 @@ impl Clone for ConcreteObject {
             children: self.children.clone(),
             classification_method: self.classification_method.clone_box(),
             extraction_method: self.extraction_method.clone_box(),
+            classification_signature: self.classification_signature,
+            extraction_signature: self.extraction_signature,
             obj_type: self.obj_type.clone(),
             expected_children: self.expected_children.clone(),
+            candidate_ranking: self.candidate_ranking.clone(),
         }
     }
 }
@@ -463,15 +861,30 @@ impl Clone for ConcretePair {
 
 pub struct ConcreteObjectBuilder {
     cache: ObjectCache,
+    /// `SharedData` conversions available to callers extracting a child
+    /// whose declared type differs from what a parent actually published.
+    /// Empty by default; populate via [`ConcreteObjectBuilder::register_coercion`].
+    coercions: CoercionTable,
 }
 
 impl ConcreteObjectBuilder {
     pub fn new() -> Self {
         Self {
             cache: DashMap::new(),
+            coercions: CoercionTable::new(),
         }
     }
 
+    /// Registers a `SharedData` conversion `T -> U` for later lookup
+    /// through [`ConcreteObjectBuilder::coercions`].
+    pub fn register_coercion<T: 'static, U: 'static>(&mut self, convert: fn(T) -> U) {
+        self.coercions.register(convert);
+    }
+
+    pub fn coercions(&self) -> &CoercionTable {
+        &self.coercions
+    }
+
     pub fn build<T, E>(&mut self) -> Arc<RwLock<ConcretePageType>>
     where
         T: Object + 'static,
@@ -514,8 +927,12 @@ impl ConcreteObjectBuilder {
         }
     }
 
-    /// Build and automatically connect parent-child relationships
-    pub fn build_with_relationships<T, E>(&mut self) -> Arc<RwLock<ConcretePageType>>
+    /// Build and automatically connect parent-child relationships.
+    /// Fails if [`ConcreteObject::collect_children_from_cache`] finds a
+    /// cycle in the cache's parent chain.
+    pub fn build_with_relationships<T, E>(
+        &mut self,
+    ) -> Result<Arc<RwLock<ConcretePageType>>, String>
     where
         T: Object + 'static,
         E: Error + Debug + Display + 'static,
@@ -528,7 +945,7 @@ impl ConcreteObjectBuilder {
             Err(arc) => arc.read().unwrap().clone(),
         };
 
-        obj_mut.collect_children_from_cache(&self.cache);
+        obj_mut.collect_children_from_cache(&self.cache)?;
 
         let updated_obj = Arc::new(RwLock::new(obj_mut));
         let page_type = if T::KEY_PAGE {
@@ -546,7 +963,7 @@ impl ConcreteObjectBuilder {
         };
 
         self.cache.insert(T::TYPE.id, page_type.clone());
-        page_type
+        Ok(page_type)
     }
 
     pub fn get_cache(&self) -> &ObjectCache {
@@ -601,11 +1018,14 @@ impl ConcreteRoot {
         self.validate_root_child::<T, E>()?;
 
         if T::KEY_PAGE {
-            let page = ConcretePageType::Key(ConcreteKeyPage::new::<T, E>(&mut self.cache));
+            let page = ConcretePageType::Key(
+                ConcreteKeyPage::new::<T, E>(&mut self.cache).map_err(|e| e.to_string())?,
+            );
             self.children.push(Arc::new(RwLock::new(page)));
         } else {
-            let page =
-                ConcretePageType::Inferred(ConcreteInferredPage::new::<T, E>(&mut self.cache));
+            let page = ConcretePageType::Inferred(
+                ConcreteInferredPage::new::<T, E>(&mut self.cache).map_err(|e| e.to_string())?,
+            );
             self.children.push(Arc::new(RwLock::new(page)));
         }
 
@@ -659,52 +1079,43 @@ impl ConcreteRoot {
         Ok(())
     }
 
-    /// Connect all parent-child relationships based on the cache
-    pub fn connect_relationships(&mut self) {
-        let mut all_objects: std::collections::HashMap<TypeId, Arc<RwLock<ConcretePageType>>> =
-            std::collections::HashMap::new();
-
+    /// Connect all parent-child relationships based on the cache.
+    ///
+    /// Resolves the whole cache's parent/child edges in one deterministic
+    /// pass via [`resolve_children`] — a lock-free-at-rest snapshot plus a
+    /// union-find acyclicity check — instead of the ad-hoc locked scan this
+    /// used to do, and rejects a cyclic parent chain with an error instead
+    /// of silently producing a partial tree.
+    pub fn connect_relationships(&mut self) -> Result<(), String> {
+        let children_by_parent = resolve_children(&self.cache)?;
+
+        let mut all_objects: HashMap<TypeId, Arc<RwLock<ConcretePageType>>> = HashMap::new();
         for item in self.cache.iter() {
             let page_type = item.value();
-            let page_type_locked = page_type.read().unwrap();
-            let inner_obj = page_type_locked.inner();
-            let obj_type_id = inner_obj.read().unwrap().obj_type.id;
+            let obj_type_id = page_type
+                .read()
+                .unwrap()
+                .inner()
+                .read()
+                .unwrap()
+                .obj_type
+                .id;
             all_objects.insert(obj_type_id, page_type.clone());
         }
 
-        let mut children_id_map: std::collections::HashMap<TypeId, Vec<TypeId>> =
-            std::collections::HashMap::new();
-        for obj in all_objects.values() {
-            let obj_locked = obj.read().unwrap();
-            let inner_obj = obj_locked.inner();
-            let inner_obj_locked = inner_obj.read().unwrap();
-
-            if let Some(parent_arc) = &inner_obj_locked.parent {
-                let parent_locked = parent_arc.read().unwrap();
-                let parent_inner = parent_locked.inner();
-                let parent_obj = parent_inner.read().unwrap();
-                let parent_id = parent_obj.obj_type.id;
-                let child_id = inner_obj_locked.obj_type.id;
-
-                children_id_map.entry(parent_id).or_default().push(child_id);
-            }
-        }
-
-        for (parent_id, child_ids) in children_id_map {
-            if let Some(parent_page_type) = all_objects.get(&parent_id) {
-                let parent_locked = parent_page_type.read().unwrap();
-                let parent_inner = parent_locked.inner();
+        for (parent_id, child_ids) in &children_by_parent {
+            if let Some(parent_page_type) = all_objects.get(parent_id) {
+                let parent_inner = parent_page_type.read().unwrap().inner();
                 let mut parent_obj = parent_inner.write().unwrap();
 
-                parent_obj.children.clear();
-
-                for child_id in child_ids {
-                    if let Some(child_page_type) = all_objects.get(&child_id) {
-                        parent_obj.children.push(child_page_type.clone());
-                    }
-                }
+                parent_obj.children = child_ids
+                    .iter()
+                    .filter_map(|child_id| all_objects.get(child_id).cloned())
+                    .collect();
             }
         }
+
+        Ok(())
     }
 }
 
@@ -718,20 +1129,19 @@ impl Clone for ConcreteKeyPage {
 }
 
 impl ConcreteKeyPage {
-    pub fn new<T, E>(cache: &mut ObjectCache) -> Self
+    pub fn new<T, E>(cache: &mut ObjectCache) -> Result<Self, CastError>
     where
         T: Object + 'static,
         E: Error + Debug + Display + 'static,
     {
         if !T::KEY_PAGE {
-            panic!(
-                "Attempted to construct a key page, without key being `true` within object information for object {}!",
-                T::TYPE.ident
-            )
+            return Err(CastError::NotKeyPage {
+                ident: T::TYPE.ident,
+            });
         }
 
         let page_type = ConcreteObject::from_obj_with_cache::<T, E>(cache);
-        Self(page_type.read().unwrap().inner())
+        Ok(Self(page_type.read().unwrap().inner()))
     }
 
     pub fn inner(&self) -> Arc<RwLock<ConcreteObject>> {
@@ -754,20 +1164,19 @@ impl Clone for ConcreteInferredPage {
 }
 
 impl ConcreteInferredPage {
-    pub fn new<T, E>(cache: &mut ObjectCache) -> Self
+    pub fn new<T, E>(cache: &mut ObjectCache) -> Result<Self, CastError>
     where
         T: Object + 'static,
         E: Error + Debug + Display + 'static,
     {
         if !T::INFERRED_PAGE {
-            panic!(
-                "Attempted to construct an inferred page, without inferred being `true` within object information! {}",
-                T::TYPE.ident
-            )
+            return Err(CastError::NotInferredPage {
+                ident: T::TYPE.ident,
+            });
         }
 
         let page_type = ConcreteObject::from_obj_with_cache::<T, E>(cache);
-        Self(page_type.read().unwrap().inner())
+        Ok(Self(page_type.read().unwrap().inner()))
     }
 
     pub fn inner(&self) -> Arc<RwLock<ConcreteObject>> {
@@ -825,33 +1234,42 @@ mod tests {
             extraction_method: Box::new(
                 extract_fn as ExtractionMethod<SharedData, MyError, Constructed>,
             ) as Box<dyn AnyClone>,
+            classification_signature: CastSignature::of::<ClassificationMethod<SharedData, MyError>>(
+            ),
+            extraction_signature: CastSignature::of::<
+                ExtractionMethod<SharedData, MyError, Constructed>,
+            >(),
             obj_type: TypeInformation {
                 id: TypeId::of::<()>(),
                 ident: "Test",
             },
             expected_children: vec![],
+            candidate_ranking: vec![],
         };
 
-        unsafe {
-            let got_classify = obj
-                .cast_classification::<SharedData, MyError>()
-                .expect("classification cast failed");
-            let got_ptr = got_classify as *const ();
-            let want_ptr = classify_fn as *const ();
-            assert_eq!(
-                got_ptr, want_ptr,
-                "classification function pointer mismatch"
-            );
+        let got_classify = obj
+            .cast_classification::<SharedData, MyError>()
+            .expect("classification cast failed");
+        let got_ptr = got_classify as *const ();
+        let want_ptr = classify_fn as *const ();
+        assert_eq!(
+            got_ptr, want_ptr,
+            "classification function pointer mismatch"
+        );
 
-            let got_extract = obj
-                .cast_extraction::<SharedData, MyError, Constructed>()
-                .expect("extraction cast failed");
-            let got_e_ptr = got_extract as *const ();
-            let want_e_ptr = extract_fn as *const ();
-            assert_eq!(
-                got_e_ptr, want_e_ptr,
-                "extraction function pointer mismatch"
-            );
-        }
+        let got_extract = obj
+            .cast_extraction::<SharedData, MyError, Constructed>()
+            .expect("extraction cast failed");
+        let got_e_ptr = got_extract as *const ();
+        let want_e_ptr = extract_fn as *const ();
+        assert_eq!(
+            got_e_ptr, want_e_ptr,
+            "extraction function pointer mismatch"
+        );
+
+        let mismatch = obj
+            .cast_extraction::<Constructed, MyError, Constructed>()
+            .expect_err("wrong SharedData type should be refused");
+        assert!(matches!(mismatch, CastError::TypeMismatch { .. }));
     }
 }
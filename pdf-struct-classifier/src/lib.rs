@@ -1,23 +1,42 @@
 #![allow(unused)] // TODO: remove after finishing Classifier
 
+pub mod cache;
+pub mod coercion;
 pub mod config;
+pub mod fold;
+pub mod incremental;
+pub mod inference;
 pub mod instances;
+pub mod navigation;
+pub mod parallel;
+pub mod pipeline;
+pub mod reclassify;
+pub mod routing;
+pub mod scheduler;
+pub mod schema;
+pub mod sidecar;
+pub mod unify;
+pub mod visitor;
 
-#[cfg(test)]
-mod tests;
+use pdf_struct_traits::{ClassificationResult, Classify, Location, Object, TypeInformation};
 
-use pdf_struct_traits::ClassificationResult;
-
-use crate::config::Config;
-use std::any::Any;
-use std::collections::HashMap;
-use std::fmt::Debug;
+use crate::cache::CachePolicy;
+use crate::config::{ClassificationMode, Config};
+use crate::scheduler::{DependencyGraph, Pass, TaskId};
+use std::any::{Any, TypeId};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Debug, Display};
 use std::path::PathBuf;
 
 #[derive(thiserror::Error, Debug)]
 pub enum ClassiferError {
     #[error("No key objects were provided!")]
     NoKeysProvided,
+    #[error("Classification failed: {0}")]
+    ClassificationFailed(String),
+    #[error("Cycle detected while classifying {1:?} at page {0}")]
+    CyclicDependency(i32, std::any::TypeId),
 }
 
 /// Classifier is meant to bridge the context we are provided from the user
@@ -30,8 +49,33 @@ pub enum ClassiferError {
 pub struct Classifier {
     config: Config,
     path: PathBuf,
-    context: HashMap<i32, ClassificationResult<Box<dyn Any>, ClassiferError>>,
+    /// Memoization table for classification tasks, keyed by the page/type
+    /// pair they resolve. See [`Classifier::ensure`].
+    context: HashMap<TaskId, ClassificationResult<Box<dyn Any + Send + Sync>, ClassiferError>>,
+    /// Tasks currently being resolved, used to detect dependency cycles.
+    stack: Vec<TaskId>,
+    /// Resolved dependency edges, kept around so callers can dump the
+    /// inferred structure for debugging.
+    graph: DependencyGraph,
+    /// Content fingerprint each resolved task was classified from. Compared
+    /// against `prior` on the next run to decide what can be reused.
+    fingerprints: HashMap<TaskId, u64>,
+    /// The previous run's sidecar, if loaded via [`Classifier::resume_from`].
+    /// When present, `ensure` reuses a page verbatim instead of re-running
+    /// OCR whenever its fingerprint is unchanged and everything it depends
+    /// on was itself reused.
+    prior: Option<sidecar::DocumentStructure>,
+    /// Tasks that had to be recomputed this run (as opposed to reused from
+    /// `prior`), tracked so dependents can tell their dependency changed.
+    recomputed: HashSet<TaskId>,
+    /// Report of what was reused vs. recomputed this run.
+    pub incremental_report: incremental::IncrementalReport,
     pages: i32,
+    /// The starting page of each chapter (the page its `KeyPage` lives
+    /// on), registered via [`Classifier::index_chapters`] so
+    /// [`Classifier::resolve`] can turn a [`Location`] into an absolute
+    /// page without decoding every chapter before it.
+    chapter_starts: Vec<i32>,
 }
 
 impl Classifier {
@@ -40,14 +84,244 @@ impl Classifier {
             config,
             path,
             context: HashMap::new(),
+            stack: Vec::new(),
+            graph: DependencyGraph::new(),
+            fingerprints: HashMap::new(),
+            prior: None,
+            recomputed: HashSet::new(),
+            incremental_report: incremental::IncrementalReport::default(),
             pages: 0,
+            chapter_starts: Vec::new(),
         }
     }
 
+    /// Enables incremental mode: pages whose fingerprint still matches
+    /// `prior` (and whose dependencies were themselves reused) are served
+    /// from the sidecar instead of being re-classified.
+    pub fn resume_from(&mut self, prior: sidecar::DocumentStructure) {
+        self.prior = Some(prior);
+    }
+
+    /// Registers the starting page of each chapter, so [`Classifier::resolve`]
+    /// knows where `Location { chapter, .. }` begins without having
+    /// classified anything yet. `starts[i]` is chapter `i`'s `KeyPage`.
+    pub fn index_chapters(&mut self, starts: Vec<i32>) {
+        self.chapter_starts = starts;
+    }
+
+    /// Resolves `location` to an absolute page —
+    /// `chapter_starts[location.chapter] + location.page` — and classifies
+    /// that single page via [`Classifier::classify_chunk`], against every
+    /// registered type. No other page is read or classified: the chapter's
+    /// `KeyPage` is not classified first, its `InferredPage` members are not
+    /// inferred, and nothing is recorded in `context` — the same
+    /// random-access addressing mupdf's `fz_location` gives you over a
+    /// chaptered document. Chapters must be registered first via
+    /// [`Classifier::index_chapters`].
+    pub fn resolve(&mut self, location: Location) -> Result<ClassificationOutcome, ClassiferError> {
+        let start_page = *self.chapter_starts.get(location.chapter).ok_or_else(|| {
+            ClassiferError::ClassificationFailed(format!(
+                "chapter {} was never indexed via `index_chapters`",
+                location.chapter
+            ))
+        })?;
+
+        self.classify_chunk(start_page + location.page as i32)
+    }
+
+    /// Scans `pages` into `KeyPage`-delimited segments via `is_key_page`,
+    /// then expands each segment concurrently across `threads` workers via
+    /// `expand_segment`, stitching the results back in segment order. See
+    /// [`parallel::scan_segments`]/[`parallel::build_parallel`] for the
+    /// mechanics this delegates to.
+    pub fn build_parallel(
+        &self,
+        pages: &[Vec<u8>],
+        threads: usize,
+        is_key_page: impl Fn(usize) -> bool,
+        expand_segment: impl Fn(parallel::Segment) -> Vec<(Location, TypeInformation)> + Sync,
+    ) -> Vec<(Location, TypeInformation)> {
+        let segments = parallel::scan_segments(pages.len(), is_key_page);
+        parallel::build_parallel(&segments, threads, expand_segment)
+    }
+
     pub fn begin(&self) -> Result<(), ClassiferError> {
         todo!()
     }
 
+    /// Resolves `task`, memoizing the result in `context` keyed by its
+    /// `(page, TypeId)`. A cached result from *this* run is returned as-is,
+    /// without re-running OCR. Otherwise, if incremental mode is enabled
+    /// (see [`Classifier::resume_from`]) and the task's content fingerprint
+    /// matches the prior run's while every dependency it declares was
+    /// itself reused, the prior tier/confidence is reused verbatim.
+    /// Otherwise the task (and transitively, whatever it `depends_on`) is
+    /// pushed onto `stack` so a task that re-enters itself is caught as a
+    /// cycle rather than recursing forever, reported as
+    /// [`ClassiferError::CyclicDependency`] rather than unwinding.
+    pub fn ensure(
+        &mut self,
+        task: Box<dyn Pass>,
+    ) -> &ClassificationResult<Box<dyn Any + Send + Sync>, ClassiferError> {
+        let id = task.task_id();
+
+        if self.context.contains_key(&id) {
+            return self.context.get(&id).unwrap();
+        }
+
+        if self.stack.contains(&id) {
+            return self.context.entry(id).or_insert(ClassificationResult::Err(
+                ClassiferError::CyclicDependency(id.0, id.1),
+            ));
+        }
+
+        let deps = task.depends_on();
+        for dep in &deps {
+            self.graph.record_edge(id, *dep);
+        }
+
+        let tag = self.config.tag_for(id.1);
+        let fp = incremental::fingerprint(task.content_bytes());
+        let deps_reused = deps
+            .iter()
+            .all(|dep| self.context.contains_key(dep) && !self.recomputed.contains(dep));
+
+        let reused = tag.as_deref().and_then(|tag| {
+            let prior = self.prior.as_ref()?;
+            if incremental::can_reuse(prior, id.0, tag, fp, deps_reused) {
+                prior.record_for(id.0, tag).map(reused_result_from_record)
+            } else {
+                None
+            }
+        });
+
+        self.stack.push(id);
+        let result = match reused {
+            Some(reused) => {
+                if let Some(tag) = &tag {
+                    self.incremental_report.record_reused(id.0, tag.clone());
+                }
+                reused
+            }
+            None => {
+                let result = task.run(self);
+                self.recomputed.insert(id);
+                if let Some(tag) = &tag {
+                    self.incremental_report.record_recomputed(id.0, tag.clone());
+                }
+                result
+            }
+        };
+        self.stack.pop();
+
+        self.fingerprints.insert(id, fp);
+        self.context.entry(id).or_insert(result)
+    }
+
+    /// Classifies `page_bytes` as `T`, consulting [`Config::cache`] first
+    /// when one is configured. On a hit whose `SharedData` was persisted
+    /// (see [`CachePolicy`]), `T::classify` is skipped entirely; on a
+    /// tier-only hit (or no hit at all) `classify` runs as normal, and its
+    /// result is stored back into the cache for next time.
+    pub fn classify_cached<T, E>(&self, page_bytes: &[u8]) -> ClassificationResult<T::SharedData, E>
+    where
+        T: Object + CachePolicy + 'static,
+        E: std::error::Error + Debug + Display + Send + Sync + 'static,
+    {
+        use crate::cache::{cache_key, CacheEntry, Tier};
+
+        let Some(cache) = self.config.cache() else {
+            return T::classify::<E>(page_bytes);
+        };
+
+        let key = cache_key(page_bytes, &self.config);
+
+        if let Some(entry) = cache.get(key) {
+            if let Some(data) = entry.data.as_deref().and_then(T::decode_shared_data) {
+                return match entry.tier {
+                    Tier::Confident => ClassificationResult::Confident(entry.confidence, data),
+                    Tier::Probable => ClassificationResult::Probable(entry.confidence, data),
+                    Tier::Uncertain => ClassificationResult::Uncertain(entry.confidence),
+                };
+            }
+            // Tier-only hit, or a hit whose stored bytes failed to decode:
+            // `classify` still has to run to reproduce `SharedData`.
+        }
+
+        let result = T::classify::<E>(page_bytes);
+
+        let to_store = match &result {
+            ClassificationResult::Confident(score, data) => {
+                Some((Tier::Confident, *score, Some(data)))
+            }
+            ClassificationResult::Probable(score, data) => {
+                Some((Tier::Probable, *score, Some(data)))
+            }
+            ClassificationResult::Uncertain(score) => Some((Tier::Uncertain, *score, None)),
+            ClassificationResult::Err(_) => None,
+        };
+
+        if let Some((tier, confidence, data)) = to_store {
+            cache.put(
+                key,
+                CacheEntry {
+                    tier,
+                    confidence,
+                    data: data.and_then(T::encode_shared_data),
+                },
+            );
+        }
+
+        result
+    }
+
+    /// Routes `result` through `type_tag`'s configured
+    /// [`routing::ConfidenceThresholds`], deciding whether it's definitive
+    /// enough to commit or needs to be escalated to a heavier classifier.
+    /// See [`routing::route`].
+    pub(crate) fn route<T, E>(
+        &self,
+        type_tag: &str,
+        result: ClassificationResult<T, E>,
+    ) -> routing::Routed<T, E>
+    where
+        T: Send + Sync,
+        E: Error + Debug + Display,
+    {
+        routing::route(result, &self.config.threshold_for(type_tag))
+    }
+
+    /// A debug dump of every dependency edge resolved so far, in Graphviz
+    /// DOT format.
+    pub fn dependency_dot(&self) -> String {
+        self.graph.to_dot()
+    }
+
+    /// Serializes every resolved classification into a
+    /// [`sidecar::DocumentStructure`], keyed by type tag rather than the
+    /// process-local `TypeId`, so it can be written to disk and reloaded in
+    /// a later run (or process) without re-linking MuPDF or re-running OCR.
+    pub fn structure(&self) -> sidecar::DocumentStructure {
+        use sidecar::Tier;
+
+        let pages = self.context.iter().filter_map(|((page, type_id), result)| {
+            let tag = self.config.tag_for(*type_id)?;
+
+            let (tier, confidence) = match result {
+                ClassificationResult::Confident(score, _) => (Tier::Confident, Some(*score)),
+                ClassificationResult::Probable(score, _) => (Tier::Probable, Some(*score)),
+                ClassificationResult::Uncertain(score) => (Tier::Uncertain, Some(*score)),
+                ClassificationResult::Err(_) => (Tier::Err, None),
+            };
+
+            let fingerprint = self.fingerprints.get(&(*page, *type_id)).copied();
+
+            Some((*page, tag, tier, confidence, fingerprint))
+        });
+
+        sidecar::DocumentStructure::from_pages(&self.config, pages)
+    }
+
     /// We specify chunks as each unique key object that is a child of root.
     /// (child as in first-generation child, nothing that is a child of a child of root is counted.)
     /// I.e:
@@ -56,7 +330,136 @@ impl Classifier {
     ///           |- SubChapter
     ///       |- SomeOtherKey
     ///           |- SomeOtherKeysChild
-    fn classify_chunk(&self, start_page: i32) -> Result<(), ClassiferError> {
-        todo!()
+    fn classify_chunk(&self, start_page: i32) -> Result<ClassificationOutcome, ClassiferError> {
+        match self.config.mode() {
+            ClassificationMode::DryRun => Ok(ClassificationOutcome::DryRun(
+                self.dry_run_chunk(start_page),
+            )),
+            ClassificationMode::Check | ClassificationMode::Full => {
+                let bytes = self.page_bytes(start_page)?;
+                let candidates = self.config.candidates();
+
+                if candidates.is_empty() {
+                    return Err(ClassiferError::NoKeysProvided);
+                }
+
+                // More than one `Candidate` can be registered for the same
+                // type (distinct `Classify` impls run against it); group by
+                // type first so each type is folded through
+                // `routing::combine_ensemble` exactly once before `route`
+                // ever sees it.
+                let mut by_type: HashMap<TypeId, Vec<&instances::Candidate<ClassiferError>>> =
+                    HashMap::new();
+                for candidate in candidates {
+                    by_type
+                        .entry(candidate.type_info().id)
+                        .or_default()
+                        .push(candidate);
+                }
+
+                let results = by_type
+                    .into_values()
+                    .map(|group| {
+                        let tag = group[0].type_info().ident;
+                        let results = group
+                            .iter()
+                            .map(|candidate| candidate.run_classify(&bytes))
+                            .collect();
+                        let combined =
+                            routing::combine_ensemble(results, self.config.ensemble_strategy());
+
+                        ChunkCandidateResult {
+                            type_tag: tag.to_string(),
+                            routed: self.route(tag, combined),
+                        }
+                    })
+                    .collect();
+
+                Ok(ClassificationOutcome::Classified(results))
+            }
+        }
+    }
+
+    /// Reads `page`'s rendered bytes for `classify_chunk` to classify.
+    ///
+    /// This crate has no page-rendering backend: the `extractor` crate
+    /// renders pages but is not called from here, so this panics (`todo!`)
+    /// and `classify_chunk`'s `Check`/`Full` dispatch cannot complete.
+    fn page_bytes(&self, page: i32) -> Result<Vec<u8>, ClassiferError> {
+        let _ = page;
+        todo!("wire this up to a real page-rendering backend, e.g. `extractor`")
+    }
+
+    /// Reports, for `start_page`, every registered object type that would be
+    /// attempted and which declared patterns apply, without reading the page
+    /// or calling into the extractor at all.
+    fn dry_run_chunk(&self, start_page: i32) -> Vec<DryRunCandidate> {
+        self.config
+            .dry_run_candidates()
+            .into_iter()
+            .map(|(type_tag, patterns)| DryRunCandidate {
+                page: start_page,
+                type_tag,
+                patterns,
+            })
+            .collect()
+    }
+}
+
+/// What `classify_chunk` produced, shaped by the [ClassificationMode] the
+/// classifier was built with.
+pub enum ClassificationOutcome {
+    /// The object types that would be attempted against the chunk's pages,
+    /// and which patterns apply, produced by [ClassificationMode::DryRun].
+    DryRun(Vec<DryRunCandidate>),
+    /// Every registered type's (ensemble-folded) classification result
+    /// against the chunk's `KeyPage`, each already routed to a commit/
+    /// escalate decision. Produced by [ClassificationMode::Check]/
+    /// [ClassificationMode::Full].
+    Classified(Vec<ChunkCandidateResult>),
+}
+
+/// One registered type's outcome within [`ClassificationOutcome::Classified`].
+pub struct ChunkCandidateResult {
+    pub type_tag: String,
+    pub routed: routing::Routed<Box<dyn Any + Send + Sync>, ClassiferError>,
+}
+
+/// A single page/type pairing reported by [ClassificationMode::DryRun].
+pub struct DryRunCandidate {
+    pub page: i32,
+    /// Tag of the object type that would be attempted against `page`.
+    pub type_tag: String,
+    /// Human-readable descriptions of the [pdf_struct_traits::Pattern]s this
+    /// type declares, if it's a pair type.
+    pub patterns: Vec<String>,
+}
+
+/// Rehydrates a reused [`sidecar::PageRecord`] into a
+/// [`ClassificationResult`]. The original `SharedData` produced by
+/// `Classify::classify` isn't persisted in the sidecar (only the tier and
+/// confidence are), so a reused result carries a unit placeholder; any step
+/// that needs the real `SharedData` must fall back to a full classify.
+fn reused_result_from_record(
+    record: &sidecar::PageRecord,
+) -> ClassificationResult<Box<dyn Any + Send + Sync>, ClassiferError> {
+    let placeholder = || Box::new(()) as Box<dyn Any + Send + Sync>;
+
+    match record.tier {
+        sidecar::Tier::Confident => {
+            ClassificationResult::Confident(record.confidence.unwrap_or(100.0), placeholder())
+        }
+        sidecar::Tier::Probable => {
+            ClassificationResult::Probable(record.confidence.unwrap_or(50.0), placeholder())
+        }
+        sidecar::Tier::Uncertain => {
+            ClassificationResult::Uncertain(record.confidence.unwrap_or(0.0))
+        }
+        sidecar::Tier::Err => {
+            ClassificationResult::Err(ClassiferError::ClassificationFailed(
+                "reused from a sidecar record that recorded a prior classification failure"
+                    .to_string(),
+            ))
+        }
     }
 }
@@ -0,0 +1,173 @@
+//! `pdf_struct_traits::Parent`/`Child` describe the hierarchy at the type
+//! level, but nothing walks it once a document is actually built — every
+//! caller that wants "the next sibling" or "the enclosing chapter" ends up
+//! re-deriving it from [`ConcreteObject::parent`]/[`ConcreteObject::children`]
+//! by hand. [`SimpleNodeIterator`] is the one cursor behind `children`,
+//! `ancestors`, `following_siblings`, and `preceding_siblings` below: each
+//! just picks a starting node and a `next_node` fn pointer and lets the same
+//! iterator step through it, forwards, backwards, or upwards.
+
+use std::sync::{Arc, RwLock};
+
+use crate::instances::ConcretePageType;
+
+/// Advances a [`SimpleNodeIterator`] from one node to the next, in whatever
+/// direction the iterator was built with.
+type NextNode = fn(&Arc<RwLock<ConcretePageType>>) -> Option<Arc<RwLock<ConcretePageType>>>;
+
+/// A cursor over a document tree, reused for every direction `children`,
+/// `ancestors`, `following_siblings`, and `preceding_siblings` expose —
+/// only the starting node and the `next_node` fn pointer differ.
+pub struct SimpleNodeIterator {
+    current: Option<Arc<RwLock<ConcretePageType>>>,
+    next_node: NextNode,
+}
+
+impl Iterator for SimpleNodeIterator {
+    type Item = Arc<RwLock<ConcretePageType>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current.take()?;
+        self.current = (self.next_node)(&node);
+        Some(node)
+    }
+}
+
+fn parent_of(node: &Arc<RwLock<ConcretePageType>>) -> Option<Arc<RwLock<ConcretePageType>>> {
+    let inner = node.read().unwrap().inner();
+    let object = inner.read().unwrap();
+    object.parent.clone()
+}
+
+fn siblings_of(node: &Arc<RwLock<ConcretePageType>>) -> Vec<Arc<RwLock<ConcretePageType>>> {
+    let Some(parent) = parent_of(node) else {
+        return Vec::new();
+    };
+    let inner = parent.read().unwrap().inner();
+    let object = inner.read().unwrap();
+    object.children.clone()
+}
+
+fn next_sibling(node: &Arc<RwLock<ConcretePageType>>) -> Option<Arc<RwLock<ConcretePageType>>> {
+    let siblings = siblings_of(node);
+    let index = siblings.iter().position(|n| Arc::ptr_eq(n, node))?;
+    siblings.get(index + 1).cloned()
+}
+
+fn preceding_sibling(
+    node: &Arc<RwLock<ConcretePageType>>,
+) -> Option<Arc<RwLock<ConcretePageType>>> {
+    let siblings = siblings_of(node);
+    let index = siblings.iter().position(|n| Arc::ptr_eq(n, node))?;
+    index.checked_sub(1).and_then(|i| siblings.get(i).cloned())
+}
+
+/// Iterates `node`'s direct children, in order.
+pub fn children(node: &Arc<RwLock<ConcretePageType>>) -> SimpleNodeIterator {
+    let inner = node.read().unwrap().inner();
+    let first = inner.read().unwrap().children.first().cloned();
+    SimpleNodeIterator {
+        current: first,
+        next_node: next_sibling,
+    }
+}
+
+/// Iterates `node`'s ancestors, nearest first, up to (and including) the
+/// document root's direct children.
+pub fn ancestors(node: &Arc<RwLock<ConcretePageType>>) -> SimpleNodeIterator {
+    SimpleNodeIterator {
+        current: parent_of(node),
+        next_node: parent_of,
+    }
+}
+
+/// Iterates the siblings after `node`, nearest first.
+pub fn following_siblings(node: &Arc<RwLock<ConcretePageType>>) -> SimpleNodeIterator {
+    SimpleNodeIterator {
+        current: next_sibling(node),
+        next_node: next_sibling,
+    }
+}
+
+/// Iterates the siblings before `node`, nearest first.
+pub fn preceding_siblings(node: &Arc<RwLock<ConcretePageType>>) -> SimpleNodeIterator {
+    SimpleNodeIterator {
+        current: preceding_sibling(node),
+        next_node: preceding_sibling,
+    }
+}
+
+/// Walks up from `node` to the nearest enclosing `Key` page — the section
+/// boundary `next_prev_in_section` doesn't cross — or `None` if nothing
+/// above `node` is one.
+fn enclosing_section(
+    node: &Arc<RwLock<ConcretePageType>>,
+) -> Option<Arc<RwLock<ConcretePageType>>> {
+    let mut current = parent_of(node);
+    while let Some(candidate) = current {
+        if matches!(&*candidate.read().unwrap(), ConcretePageType::Key(_)) {
+            return Some(candidate);
+        }
+        current = parent_of(&candidate);
+    }
+    None
+}
+
+/// Flattens every node reachable from `root` (`root` included) in
+/// pre-order, locking one guard at a time the same way
+/// [`crate::visitor::walk`] does.
+fn flatten(root: &Arc<RwLock<ConcretePageType>>, out: &mut Vec<Arc<RwLock<ConcretePageType>>>) {
+    out.push(root.clone());
+
+    let children = {
+        let inner = root.read().unwrap().inner();
+        let object = inner.read().unwrap();
+        object.children.clone()
+    };
+
+    for child in &children {
+        flatten(child, out);
+    }
+}
+
+/// The node immediately before and after `node`, in document order, within
+/// `node`'s enclosing `Key` page section — `node` never sees past that
+/// boundary, so asking for the next/prev of a `Diagram`–`DataTable` pair
+/// inside a `SubChapter` can't accidentally hand back a node from the next
+/// chapter. Returns `(None, None)` if `node` has no enclosing section.
+pub fn next_prev_in_section(
+    node: &Arc<RwLock<ConcretePageType>>,
+) -> (
+    Option<Arc<RwLock<ConcretePageType>>>,
+    Option<Arc<RwLock<ConcretePageType>>>,
+) {
+    let Some(section) = enclosing_section(node) else {
+        return (None, None);
+    };
+
+    let mut order = Vec::new();
+    flatten(&section, &mut order);
+
+    let Some(index) = order.iter().position(|n| Arc::ptr_eq(n, node)) else {
+        return (None, None);
+    };
+
+    let prev = index.checked_sub(1).and_then(|i| order.get(i).cloned());
+    let next = order.get(index + 1).cloned();
+    (prev, next)
+}
+
+/// Walks `node`'s ancestors (nearest first, `node` itself not included)
+/// looking for the first one that has a value for some
+/// [`pdf_struct_traits::Inheritable<C>`] context, stopping at the first
+/// `Some`. Getting from a type-erased node back to a concrete `C` needs
+/// the caller's own `C` at the call site — the same gap
+/// [`crate::instances::ConcreteObject::cast_extraction`] exists to close —
+/// so the actual per-ancestor lookup is left to `lookup` rather than this
+/// walk trying to downcast anything itself.
+pub fn resolve_inherited<C>(
+    node: &Arc<RwLock<ConcretePageType>>,
+    mut lookup: impl FnMut(&Arc<RwLock<ConcretePageType>>) -> Option<C>,
+) -> Option<C> {
+    ancestors(node).find_map(|ancestor| lookup(&ancestor))
+}
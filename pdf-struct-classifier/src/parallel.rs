@@ -0,0 +1,92 @@
+//! Turns the type-level `KeyPage`/`InferredPage`/`PairWith` design into an
+//! actual parallel construction pass. [`scan_segments`] cuts the page
+//! stream into `KeyPage`-delimited segments in one sequential pass — the
+//! only part of this that has to run in order, since nothing can be
+//! inferred past a `KeyPage` until the next one is found — and numbers
+//! them like Parquet row groups so [`build_parallel`] can hand each one to
+//! its own worker. Actually expanding a segment's `InferredPage` members
+//! still needs the caller's concrete `Object`/`Classify`/`Extract` types at
+//! the call site (the same gap [`crate::instances::ConcreteObject::cast_extraction`]
+//! exists to close elsewhere), so that expansion is supplied as
+//! `expand_segment` rather than reimplemented here.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+use pdf_struct_traits::{Location, TypeInformation};
+
+/// One `KeyPage`-delimited segment of the raw page stream, numbered like a
+/// Parquet row group so segments can be distributed across a thread pool
+/// and their results stitched back in order afterwards. `start` is the
+/// page its own `KeyPage` was detected on; `end` is exclusive — either the
+/// next segment's `start`, or the page count for the last segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub index: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Segment {
+    /// The pages between this segment's `KeyPage` and the next one —
+    /// candidates for `InferredPage` expansion.
+    pub fn inferred_pages(&self) -> std::ops::Range<usize> {
+        (self.start + 1)..self.end
+    }
+}
+
+/// Cuts `page_count` pages into segments delimited by whatever pages
+/// `is_key_page` reports as a `KeyPage` boundary — the one sequential pass
+/// this algorithm needs, since nothing past an undetected `KeyPage` can be
+/// safely inferred yet. Detecting a `KeyPage` itself still needs a
+/// concrete `T`/`E` at the call site, so `is_key_page` is the caller's own
+/// classification result for page `i`, not something this scan derives.
+pub fn scan_segments(page_count: usize, is_key_page: impl Fn(usize) -> bool) -> Vec<Segment> {
+    let starts: Vec<usize> = (0..page_count).filter(|&page| is_key_page(page)).collect();
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(index, &start)| {
+            let end = starts.get(index + 1).copied().unwrap_or(page_count);
+            Segment { index, start, end }
+        })
+        .collect()
+}
+
+/// Expands every segment concurrently across `threads` worker threads —
+/// `expand_segment` does the actual `PairWith::SEQUENCE`-driven
+/// `InferredPage` placement within one segment (`First` precedes its
+/// paired object, `Last` follows it), run once per segment on whichever
+/// worker claims it — then stitches the results back into segment order.
+/// [`scan_segments`]'s sequential pass (and thus every segment's
+/// start/end boundary) must already be resolved before this runs: the
+/// critical invariant that lets a worker start on a segment without
+/// waiting on its neighbours.
+pub fn build_parallel(
+    segments: &[Segment],
+    threads: usize,
+    expand_segment: impl Fn(Segment) -> Vec<(Location, TypeInformation)> + Sync,
+) -> Vec<(Location, TypeInformation)> {
+    let threads = threads.max(1).min(segments.len().max(1));
+    let next = AtomicUsize::new(0);
+    let results: Vec<RwLock<Vec<(Location, TypeInformation)>>> =
+        segments.iter().map(|_| RwLock::new(Vec::new())).collect();
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                let Some(segment) = segments.get(i) else {
+                    break;
+                };
+                *results[i].write().unwrap() = expand_segment(*segment);
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .flat_map(|cell| cell.into_inner().unwrap())
+        .collect()
+}
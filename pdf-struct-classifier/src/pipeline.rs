@@ -0,0 +1,100 @@
+//! A composable graph of async processing steps (render -> classify ->
+//! extract -> post-process), meant to eventually back [`crate::Classifier`]
+//! in place of a single fixed loop.
+//!
+//! Each [`ProcessingStep`] is deliberately narrow — one input type, one
+//! output type, one shared context type — so a caller can slot in their own
+//! stage (OCR cleanup, dedup, export) anywhere between the built-in ones by
+//! chaining it onto a [`Pipeline`] with [`Pipeline::then`]. Steps are `async`
+//! so a classify step can await remote or GPU-backed inference without
+//! blocking whatever is driving the pipeline (e.g. the page-rendering loop
+//! in `extractor`).
+
+use async_trait::async_trait;
+
+#[derive(thiserror::Error, Debug)]
+pub enum StepError {
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// One stage of a [`Pipeline`]. `Ctx` is threaded by `&mut` reference through
+/// every step in a chain, so steps that need to share state (a running page
+/// count, a handle back to the `Classifier`) agree on the same `Ctx` type;
+/// `Input`/`Output` differ per step and are what [`Pipeline::then`] lines up
+/// when chaining two steps together.
+#[async_trait]
+pub trait ProcessingStep: Send + Sync {
+    type Input: Send;
+    type Output: Send;
+    type Ctx: Send;
+
+    async fn process(
+        &self,
+        input: Self::Input,
+        ctx: &mut Self::Ctx,
+    ) -> Result<Self::Output, StepError>;
+}
+
+/// Runs `first` then feeds its output into `second`, short-circuiting on the
+/// first error. Built by [`Pipeline::then`]; not meant to be constructed
+/// directly.
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+#[async_trait]
+impl<A, B> ProcessingStep for Chain<A, B>
+where
+    A: ProcessingStep,
+    B: ProcessingStep<Ctx = A::Ctx, Input = A::Output>,
+{
+    type Input = A::Input;
+    type Output = B::Output;
+    type Ctx = A::Ctx;
+
+    async fn process(
+        &self,
+        input: Self::Input,
+        ctx: &mut Self::Ctx,
+    ) -> Result<Self::Output, StepError> {
+        let mid = self.first.process(input, ctx).await?;
+        self.second.process(mid, ctx).await
+    }
+}
+
+/// A runnable chain of [`ProcessingStep`]s, built up one stage at a time via
+/// [`Pipeline::then`]. The whole chain is itself a single [`ProcessingStep`]
+/// (`S`), so running it is just calling `process` once on the composed step.
+pub struct Pipeline<S> {
+    step: S,
+}
+
+impl<S> Pipeline<S>
+where
+    S: ProcessingStep,
+{
+    /// Starts a pipeline from its first stage.
+    pub fn new(step: S) -> Self {
+        Self { step }
+    }
+
+    /// Appends `next` to the end of the pipeline. `next` must accept this
+    /// pipeline's current output as its input and share its context type.
+    pub fn then<B>(self, next: B) -> Pipeline<Chain<S, B>>
+    where
+        B: ProcessingStep<Ctx = S::Ctx, Input = S::Output>,
+    {
+        Pipeline::new(Chain {
+            first: self.step,
+            second: next,
+        })
+    }
+
+    /// Runs the whole chain against `input`, short-circuiting on the first
+    /// stage that returns a [`StepError`].
+    pub async fn run(&self, input: S::Input, ctx: &mut S::Ctx) -> Result<S::Output, StepError> {
+        self.step.process(input, ctx).await
+    }
+}
@@ -0,0 +1,147 @@
+//! Building and classifying a [`ConcreteRoot`] is otherwise fully
+//! synchronous. [`RootHandle::spawn`] hands the walk to its own thread so a
+//! caller streaming in a partially-loaded document can [`RootHandle::restart`]
+//! or [`RootHandle::cancel`] an in-flight pass without blocking, instead of
+//! waiting for a pass over stale bytes to finish first.
+
+use std::any::TypeId;
+use std::sync::{Arc, RwLock};
+use std::thread::JoinHandle;
+
+use crossbeam::channel::{self, Receiver, Sender};
+
+use crate::instances::{ConcretePageType, ConcreteRoot};
+
+/// Sent on [`RootHandle`]'s control channel to redirect or stop the actor.
+pub enum StateChange {
+    /// Abandon whatever's in flight and start a fresh pass over the root's
+    /// current children.
+    Restart,
+    /// Abandon whatever's in flight and don't schedule a replacement.
+    Cancel,
+}
+
+/// Emitted on [`RootHandle`]'s progress channel as a pass runs.
+#[derive(Debug, Clone)]
+pub enum Progress {
+    DidStart,
+    DidCheckObject(TypeId),
+    DidFinish,
+    /// A pass couldn't even begin walking the root (its lock was
+    /// poisoned by a panic elsewhere); the actor gives up rather than
+    /// looping forever against a tree it can no longer read.
+    DidFailToRestart,
+}
+
+/// Handle to a [`RootHandle::spawn`]ed actor: send [`StateChange`]s in,
+/// read [`Progress`] out. Dropping it cancels the actor and joins its
+/// thread.
+pub struct RootHandle {
+    control: Sender<StateChange>,
+    progress: Receiver<Progress>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl RootHandle {
+    /// Spawns the actor on its own thread, walking `root`'s children and
+    /// reporting [`Progress`] until cancelled.
+    pub fn spawn(root: Arc<RwLock<ConcreteRoot>>) -> Self {
+        let (control_tx, control_rx) = channel::unbounded();
+        let (progress_tx, progress_rx) = channel::unbounded();
+
+        let worker = std::thread::spawn(move || run(root, control_rx, progress_tx));
+
+        Self {
+            control: control_tx,
+            progress: progress_rx,
+            worker: Some(worker),
+        }
+    }
+
+    /// Abandons any in-flight pass and schedules a fresh one over the
+    /// root's current children — for use when the underlying PDF bytes
+    /// changed out from under a streamed/partially-loaded document.
+    pub fn restart(&self) {
+        let _ = self.control.send(StateChange::Restart);
+    }
+
+    /// Abandons any in-flight pass without scheduling a replacement.
+    pub fn cancel(&self) {
+        let _ = self.control.send(StateChange::Cancel);
+    }
+
+    /// Returns the next [`Progress`] event without blocking, if one's
+    /// already been emitted.
+    pub fn try_recv_progress(&self) -> Option<Progress> {
+        self.progress.try_recv().ok()
+    }
+
+    /// Blocks until a [`Progress`] event arrives or the actor thread exits.
+    pub fn recv_progress(&self) -> Option<Progress> {
+        self.progress.recv().ok()
+    }
+}
+
+impl Drop for RootHandle {
+    fn drop(&mut self) {
+        let _ = self.control.send(StateChange::Cancel);
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// The actor's main loop. Walks `root`'s cached children, checking for a
+/// [`StateChange`] between each node so a `Cancel`/`Restart` takes effect
+/// promptly instead of waiting for the whole tree to finish.
+///
+/// Confirming and reporting each node's identity is as far as this walk
+/// goes today: actually invoking a node's classification/extraction method
+/// requires the caller's concrete `T`/`E` at the call site (see
+/// [`crate::instances::ConcreteObject::cast_classification`] and
+/// `cast_extraction`), which `classify_chunk` sidesteps through the
+/// type-erased candidates registered on its `Config`. A caller-supplied per-type dispatch table would
+/// close it without changing this actor's shape.
+fn run(
+    root: Arc<RwLock<ConcreteRoot>>,
+    control: Receiver<StateChange>,
+    progress: Sender<Progress>,
+) {
+    loop {
+        let _ = progress.send(Progress::DidStart);
+
+        let nodes: Vec<Arc<RwLock<ConcretePageType>>> = match root.read() {
+            Ok(guard) => guard.children.clone(),
+            Err(_) => {
+                let _ = progress.send(Progress::DidFailToRestart);
+                return;
+            }
+        };
+
+        let mut restarted = false;
+        for node in nodes {
+            match control.try_recv() {
+                Ok(StateChange::Cancel) => return,
+                Ok(StateChange::Restart) => {
+                    restarted = true;
+                    break;
+                }
+                Err(_) => {}
+            }
+
+            let obj_type_id = node.read().unwrap().inner().read().unwrap().obj_type.id;
+            let _ = progress.send(Progress::DidCheckObject(obj_type_id));
+        }
+
+        if restarted {
+            continue;
+        }
+
+        let _ = progress.send(Progress::DidFinish);
+
+        match control.recv() {
+            Ok(StateChange::Restart) => continue,
+            Ok(StateChange::Cancel) | Err(_) => return,
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Makes the confidence tiers on [`ClassificationResult`] actionable rather
+//! than advisory: [`route`] decides whether a result cleared a configurable
+//! threshold or needs to be escalated to a heavier classifier, and
+//! [`combine_ensemble`] folds several classifiers' attempts at the same
+//! candidate type into one result before either of those decisions run.
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use pdf_struct_traits::{ClassificationResult, ConfidenceScore};
+
+/// Per-type confidence cutoffs consulted by [`route`]. Distinct from the
+/// `Confident`/`Probable`/`Uncertain` tiers `Classify::classify` itself
+/// chooses — a result can be tier `Probable` yet still clear (or miss) a
+/// type's configured `confident` cutoff, which is what actually decides
+/// whether it's committed as-is or escalated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfidenceThresholds {
+    /// Minimum score to treat a result as definitive and commit it.
+    /// Anything below this is routed to [`Routed::Escalate`].
+    pub confident: ConfidenceScore,
+}
+
+impl Default for ConfidenceThresholds {
+    /// Mirrors the boundary [`ClassificationResult::Confident`] itself
+    /// documents (>90% confidence).
+    fn default() -> Self {
+        Self { confident: 90.0 }
+    }
+}
+
+/// How [`combine_ensemble`] folds multiple classifiers' scores for the same
+/// candidate type into one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnsembleStrategy {
+    /// Take the highest-scoring classifier's result as-is.
+    Max,
+    /// Average every classifier's score, keeping the highest scorer's data
+    /// payload (scores can be averaged; arbitrary `SharedData` can't be).
+    Mean,
+    /// Average every classifier's score weighted by `weights` (matched to
+    /// the input results positionally; a length mismatch just ignores the
+    /// missing weights), again keeping the data of the highest scorer by its
+    /// own, unweighted score.
+    WeightedVote(Vec<f32>),
+}
+
+/// What [`route`] decided about a single [`ClassificationResult`].
+pub enum Routed<T, E>
+where
+    T: Send + Sync,
+    E: Error + Debug + Display,
+{
+    /// Cleared the configured threshold (or failed outright); commit as-is.
+    Definitive(ClassificationResult<T, E>),
+    /// Missed the configured threshold; re-run through a heavier classifier
+    /// before committing.
+    Escalate(ClassificationResult<T, E>),
+}
+
+/// Decides whether `result` is definitive enough to commit, or should be
+/// escalated to a second, heavier classifier, based on `thresholds`. A
+/// classification failure ([`ClassificationResult::Err`]) is always
+/// definitive — there's nothing for escalation to retry against.
+pub fn route<T, E>(
+    result: ClassificationResult<T, E>,
+    thresholds: &ConfidenceThresholds,
+) -> Routed<T, E>
+where
+    T: Send + Sync,
+    E: Error + Debug + Display,
+{
+    let score = match &result {
+        ClassificationResult::Confident(score, _)
+        | ClassificationResult::Probable(score, _)
+        | ClassificationResult::Uncertain(score) => Some(*score),
+        ClassificationResult::Err(_) => None,
+    };
+
+    match score {
+        Some(score) if score >= thresholds.confident => Routed::Definitive(result),
+        Some(_) => Routed::Escalate(result),
+        None => Routed::Definitive(result),
+    }
+}
+
+/// Folds several classifiers' attempts at the same candidate type into a
+/// single [`ClassificationResult`] per `strategy`. The combined score
+/// replaces whatever score each contributing result carried; the data
+/// payload is taken from whichever contributing result scored highest,
+/// since arbitrary `SharedData` can't be merged the way scores can.
+///
+/// # Panics
+///
+/// Panics if `results` is empty — there's nothing to combine.
+pub fn combine_ensemble<T, E>(
+    results: Vec<ClassificationResult<T, E>>,
+    strategy: &EnsembleStrategy,
+) -> ClassificationResult<T, E>
+where
+    T: Send + Sync,
+    E: Error + Debug + Display,
+{
+    assert!(
+        !results.is_empty(),
+        "combine_ensemble called with no results"
+    );
+
+    let mut scored: Vec<(ConfidenceScore, ClassificationResult<T, E>)> = Vec::new();
+    // `results[i]`'s index, kept alongside `scored` so `WeightedVote` can
+    // zip weights against the positions they were actually declared for.
+    let mut scored_indices: Vec<usize> = Vec::new();
+    let mut errors: Vec<ClassificationResult<T, E>> = Vec::new();
+
+    for (i, result) in results.into_iter().enumerate() {
+        match &result {
+            ClassificationResult::Confident(score, _)
+            | ClassificationResult::Probable(score, _)
+            | ClassificationResult::Uncertain(score) => {
+                scored.push((*score, result));
+                scored_indices.push(i);
+            }
+            ClassificationResult::Err(_) => errors.push(result),
+        }
+    }
+
+    // Every classifier failed; nothing to score, so report the first error.
+    if scored.is_empty() {
+        return errors.into_iter().next().unwrap();
+    }
+
+    let combined_score = match strategy {
+        EnsembleStrategy::Max => scored
+            .iter()
+            .map(|(score, _)| *score)
+            .fold(f32::MIN, f32::max),
+        EnsembleStrategy::Mean => {
+            scored.iter().map(|(score, _)| *score).sum::<f32>() / scored.len() as f32
+        }
+        EnsembleStrategy::WeightedVote(weights) => {
+            // `weights` is indexed against the original `results`, not
+            // `scored` (which already dropped the `Err`s) — zip on
+            // `scored_indices` so a failed classifier earlier in `results`
+            // doesn't shift every later weight onto the wrong candidate.
+            let total_weight: f32 = scored_indices.iter().filter_map(|i| weights.get(*i)).sum();
+
+            if total_weight <= 0.0 {
+                scored
+                    .iter()
+                    .map(|(score, _)| *score)
+                    .fold(f32::MIN, f32::max)
+            } else {
+                scored
+                    .iter()
+                    .zip(&scored_indices)
+                    .filter_map(|((score, _), i)| weights.get(*i).map(|weight| score * weight))
+                    .sum::<f32>()
+                    / total_weight
+            }
+        }
+    };
+
+    let (_, winner) = scored
+        .into_iter()
+        .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .expect("scored is non-empty");
+
+    rescore(winner, combined_score)
+}
+
+/// Rebuilds `result` with `combined_score` in place of whatever confidence
+/// it originally carried, preserving its tier variant and data payload.
+fn rescore<T, E>(
+    result: ClassificationResult<T, E>,
+    combined_score: ConfidenceScore,
+) -> ClassificationResult<T, E>
+where
+    T: Send + Sync,
+    E: Error + Debug + Display,
+{
+    match result {
+        ClassificationResult::Confident(_, data) => {
+            ClassificationResult::Confident(combined_score, data)
+        }
+        ClassificationResult::Probable(_, data) => {
+            ClassificationResult::Probable(combined_score, data)
+        }
+        ClassificationResult::Uncertain(_) => ClassificationResult::Uncertain(combined_score),
+        err @ ClassificationResult::Err(_) => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct MyError;
+    impl Display for MyError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "my error")
+        }
+    }
+    impl Error for MyError {}
+
+    #[test]
+    fn weighted_vote_matches_weights_to_their_own_result_despite_an_earlier_err() {
+        let results: Vec<ClassificationResult<(), MyError>> = vec![
+            ClassificationResult::Err(MyError),
+            ClassificationResult::Confident(80.0, ()),
+            ClassificationResult::Confident(60.0, ()),
+        ];
+        let strategy = EnsembleStrategy::WeightedVote(vec![1.0, 0.0, 1.0]);
+
+        let combined = combine_ensemble(results, &strategy);
+
+        match combined {
+            ClassificationResult::Confident(score, ()) => assert_eq!(score, 60.0),
+            _ => panic!("expected a Confident result"),
+        }
+    }
+}
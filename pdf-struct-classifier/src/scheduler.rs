@@ -0,0 +1,145 @@
+use pdf_struct_traits::{ClassificationResult, Object};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use crate::ClassiferError;
+
+/// Uniquely identifies a classification task: a page paired with the
+/// object type being tested against it.
+pub type TaskId = (i32, TypeId);
+
+/// Records resolved dependency edges in the style of a `petgraph` adjacency
+/// list, so callers can dump the inferred structure for debugging.
+///
+/// `edges[from]` holds every task that `from` depended on in order to run.
+#[derive(Default)]
+pub struct DependencyGraph {
+    edges: HashMap<TaskId, Vec<TaskId>>,
+}
+
+impl DependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record_edge(&mut self, from: TaskId, to: TaskId) {
+        self.edges.entry(from).or_default().push(to);
+    }
+
+    /// Dumps the resolved dependency graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph classification {\n");
+        for (from, tos) in &self.edges {
+            for to in tos {
+                out.push_str(&format!(
+                    "  \"p{}:{:?}\" -> \"p{}:{:?}\";\n",
+                    from.0, from.1, to.0, to.1
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// A single classification task the scheduler can run, modeled on a
+/// build-system `Step`.
+///
+/// `run` is invoked by [`Classifier::ensure`], which records the declared
+/// dependencies as graph edges but does not resolve them first; a pass that
+/// needs a dependency's result gets it through `ctx` (via
+/// [`Classifier::ensure`], which returns an already-memoized result as-is).
+pub trait Pass {
+    /// The `(page, TypeId)` this pass resolves, used for memoization and
+    /// cycle detection.
+    fn task_id(&self) -> TaskId;
+
+    /// Tasks that must already be present in the classifier's context
+    /// before this pass runs (a `SubChapter` depends on its `Chapter`
+    /// parent; a `DataTable` depends on the preceding `Diagram` pair).
+    fn depends_on(&self) -> Vec<TaskId> {
+        Vec::new()
+    }
+
+    /// The raw page bytes this pass classifies against, if any. Used to
+    /// compute the content fingerprint that drives incremental
+    /// re-classification; a pass with no bytes of its own (e.g. one that
+    /// only combines already-classified neighbours) can leave this empty.
+    fn content_bytes(&self) -> &[u8] {
+        &[]
+    }
+
+    fn run(
+        self: Box<Self>,
+        ctx: &mut crate::Classifier,
+    ) -> ClassificationResult<Box<dyn Any + Send + Sync>, ClassiferError>;
+}
+
+/// A [`Pass`] that classifies a single page against object type `T`,
+/// declaring `T::Parent`/`T::Pair` as dependency edges when they aren't the
+/// `()` sentinel.
+pub struct ObjectPass<T, E> {
+    page: i32,
+    /// Page the parent/pair dependency (if any) is expected to have already
+    /// been resolved against.
+    dependency_page: i32,
+    bytes: Vec<u8>,
+    _marker: std::marker::PhantomData<fn() -> (T, E)>,
+}
+
+impl<T, E> ObjectPass<T, E> {
+    pub fn new(page: i32, dependency_page: i32, bytes: Vec<u8>) -> Self {
+        Self {
+            page,
+            dependency_page,
+            bytes,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, E> Pass for ObjectPass<T, E>
+where
+    T: Object + Send + Sync + 'static,
+    T::SharedData: 'static,
+    E: Error + Debug + Display + Send + Sync + 'static,
+{
+    fn task_id(&self) -> TaskId {
+        (self.page, T::TYPE.id)
+    }
+
+    fn depends_on(&self) -> Vec<TaskId> {
+        let mut deps = Vec::new();
+        if T::Parent::TYPE.id != TypeId::of::<()>() {
+            deps.push((self.dependency_page, T::Parent::TYPE.id));
+        }
+        if T::Pair::TYPE.id != TypeId::of::<()>() {
+            deps.push((self.dependency_page, T::Pair::TYPE.id));
+        }
+        deps
+    }
+
+    fn content_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn run(
+        self: Box<Self>,
+        _ctx: &mut crate::Classifier,
+    ) -> ClassificationResult<Box<dyn Any + Send + Sync>, ClassiferError> {
+        match T::classify::<E>(&self.bytes) {
+            ClassificationResult::Confident(score, data) => {
+                ClassificationResult::Confident(score, Box::new(data) as Box<dyn Any + Send + Sync>)
+            }
+            ClassificationResult::Probable(score, data) => {
+                ClassificationResult::Probable(score, Box::new(data) as Box<dyn Any + Send + Sync>)
+            }
+            ClassificationResult::Uncertain(score) => ClassificationResult::Uncertain(score),
+            ClassificationResult::Err(e) => {
+                ClassificationResult::Err(ClassiferError::ClassificationFailed(e.to_string()))
+            }
+        }
+    }
+}
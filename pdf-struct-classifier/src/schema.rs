@@ -0,0 +1,165 @@
+//! Serializable export of the registered object graph — every type
+//! `ConfigBuilder::with_obj`/`with_root` pulled in, as nodes plus
+//! parent/child and pair edges — so a caller can inspect or visualize what
+//! they declared without reaching into [`crate::instances::ConcretePageType`]
+//! directly. See [`crate::config::Config::export_schema`].
+//!
+//! A type's role (root/key/inferred/pair) is already implied by which
+//! [`ConcretePageType`](crate::instances::ConcretePageType) variant it was
+//! built into at `with_obj`/`with_root` time, so [`Schema`] reads it back
+//! from there rather than threading a second "role" table through the
+//! builder.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::Serialize;
+
+/// What a [`SchemaNode`] was registered as.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// The document's root, registered via [`crate::config::ConfigBuilder::with_root`].
+    Root,
+    /// A [`pdf_struct_traits::KeyPage`] — must be classified, never inferred.
+    Key,
+    /// A [`pdf_struct_traits::InferredPage`] — can be inferred from context.
+    Inferred,
+    /// One side of a [`pdf_struct_traits::PairWith`] relationship.
+    Pair,
+}
+
+/// One registered type, keyed by its tag (see [`pdf_struct_traits::Encodable::TAG`]).
+#[derive(Serialize, Clone, Debug)]
+pub struct SchemaNode {
+    pub ident: String,
+    pub role: Role,
+    /// Tags of every [`pdf_struct_traits::Object::CHILDREN`] this type can
+    /// parent, with the `()` sentinel already filtered out.
+    pub children: Vec<String>,
+    /// Tag of the other side of this type's [`pdf_struct_traits::PairWith`]
+    /// relationship, if [`Role::Pair`].
+    pub pair: Option<String>,
+    /// Human-readable descriptions of this type's declared
+    /// [`pdf_struct_traits::Pattern`]s, as in [`crate::config::Config::dry_run_candidates`].
+    pub patterns: Vec<String>,
+}
+
+/// The full registered object graph, produced by [`crate::config::Config::export_schema`].
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Schema {
+    pub nodes: Vec<SchemaNode>,
+    /// Tag of the root document, if one was registered via `with_root`.
+    pub root: Option<String>,
+    /// Tags of every node sitting on a parent-child back edge, found by the
+    /// iterative walk in [`detect_cycles`] rather than recursing the
+    /// children graph (which would recurse forever on an actual cycle).
+    pub cycles: Vec<String>,
+}
+
+impl Schema {
+    /// Serializes this schema to pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// Renders this schema as a Graphviz DOT graph: one node per registered
+    /// type, a solid edge for each parent -> child relationship, and a
+    /// dashed undirected edge for each pair link. Nodes found on a cycle are
+    /// drawn in red regardless of role.
+    pub fn to_dot(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut dot = String::from("digraph schema {\n");
+
+        for node in &self.nodes {
+            let fill = if self.cycles.contains(&node.ident) {
+                "red"
+            } else {
+                match node.role {
+                    Role::Root => "lightblue",
+                    Role::Key => "lightyellow",
+                    Role::Inferred => "lightgreen",
+                    Role::Pair => "lavender",
+                }
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [style=filled, fillcolor={}];",
+                node.ident, fill
+            );
+        }
+
+        for node in &self.nodes {
+            for child in &node.children {
+                let _ = writeln!(dot, "  \"{}\" -> \"{}\";", node.ident, child);
+            }
+            if let Some(pair) = &node.pair {
+                let _ = writeln!(
+                    dot,
+                    "  \"{}\" -> \"{}\" [style=dashed, dir=none];",
+                    node.ident, pair
+                );
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// Flags every node that sits on a parent-child back edge, using an
+/// explicit stack of enter/exit frames (standard gray/black DFS coloring)
+/// instead of recursion, so a cyclic graph is reported rather than blowing
+/// the stack. Marks both endpoints of each back edge found; it doesn't
+/// attempt to enumerate the full cycle each belongs to.
+pub(crate) fn detect_cycles(nodes: &[SchemaNode]) -> Vec<String> {
+    enum Frame {
+        Enter(String),
+        Exit(String),
+    }
+
+    let children: HashMap<&str, &[String]> = nodes
+        .iter()
+        .map(|node| (node.ident.as_str(), node.children.as_slice()))
+        .collect();
+
+    // 0 = unvisited, 1 = on the current path, 2 = fully explored.
+    let mut state: HashMap<String, u8> = HashMap::new();
+    let mut cyclic: HashSet<String> = HashSet::new();
+
+    for node in nodes {
+        if state.get(&node.ident).copied().unwrap_or(0) != 0 {
+            continue;
+        }
+
+        let mut stack = vec![Frame::Enter(node.ident.clone())];
+        while let Some(frame) = stack.pop() {
+            match frame {
+                Frame::Enter(ident) => {
+                    if state.get(&ident).copied().unwrap_or(0) != 0 {
+                        continue;
+                    }
+                    state.insert(ident.clone(), 1);
+                    stack.push(Frame::Exit(ident.clone()));
+
+                    for child in children.get(ident.as_str()).copied().unwrap_or(&[]) {
+                        match state.get(child).copied().unwrap_or(0) {
+                            1 => {
+                                cyclic.insert(ident.clone());
+                                cyclic.insert(child.clone());
+                            }
+                            0 => stack.push(Frame::Enter(child.clone())),
+                            _ => {}
+                        }
+                    }
+                }
+                Frame::Exit(ident) => {
+                    state.insert(ident, 2);
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<String> = cyclic.into_iter().collect();
+    result.sort();
+    result
+}
@@ -0,0 +1,152 @@
+//! Serializes a classified document tree into a stable on-disk sidecar, and
+//! reloads it without needing to re-link MuPDF or re-run OCR.
+//!
+//! The live tree (`Classifier::context`, keyed by `(page, TypeId)`) isn't
+//! itself portable across process runs, since `TypeId` isn't stable across
+//! builds. Instead each [PageRecord] carries the type's [Encodable::TAG],
+//! which the `#[object]` attribute macro and `#[derive(Object)]` derive from
+//! the struct's own identifier.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use pdf_struct_traits::ConfidenceScore;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::ClassiferError;
+
+/// Which confidence tier a [PageRecord] was classified under, mirroring
+/// [pdf_struct_traits::ClassificationResult].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tier {
+    Confident,
+    Probable,
+    Uncertain,
+    Err,
+}
+
+/// One classified page within the sidecar.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PageRecord {
+    pub page: i32,
+    /// The matched type's [Encodable::TAG](pdf_struct_traits::Encodable::TAG).
+    pub type_tag: String,
+    pub tier: Tier,
+    pub confidence: Option<ConfidenceScore>,
+    /// Tag of the parent type, if this type declares one.
+    pub parent_tag: Option<String>,
+    /// Tag of the type this page is paired with, if any.
+    pub pair_tag: Option<String>,
+    /// Content fingerprint of the page bytes this record was classified
+    /// from, used by [crate::incremental] to decide whether it can be
+    /// reused verbatim on a later run. A reused result keeps the
+    /// fingerprint it matched, and a pass with no bytes of its own is
+    /// fingerprinted over empty input; `None` only where no fingerprint was
+    /// recorded for the task (e.g. one cut short as a dependency cycle).
+    pub fingerprint: Option<u64>,
+}
+
+/// A matched [pdf_struct_traits::Pattern::Pair], recorded by the types it
+/// relates rather than by `TypeId`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PatternRecord {
+    pub first_tag: String,
+    pub second_tag: String,
+}
+
+/// The fully serialized structure of a classified document.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DocumentStructure {
+    pub root_tag: Option<String>,
+    pub pages: Vec<PageRecord>,
+    pub patterns: Vec<PatternRecord>,
+}
+
+impl DocumentStructure {
+    /// Walks every registered type in `config` to build a tag registry, then
+    /// emits a [PageRecord] per entry in the classifier's resolved pages.
+    pub fn from_pages(
+        config: &Config,
+        pages: impl IntoIterator<Item = (i32, String, Tier, Option<ConfidenceScore>, Option<u64>)>,
+    ) -> Self {
+        let tags = config.type_tag_registry();
+
+        let page_records = pages
+            .into_iter()
+            .map(|(page, type_tag, tier, confidence, fingerprint)| {
+                let (parent_tag, pair_tag) = tags
+                    .get(&type_tag)
+                    .cloned()
+                    .unwrap_or((None, None));
+
+                PageRecord {
+                    page,
+                    type_tag,
+                    tier,
+                    confidence,
+                    parent_tag,
+                    pair_tag,
+                    fingerprint,
+                }
+            })
+            .collect();
+
+        Self {
+            root_tag: config.root_tag(),
+            pages: page_records,
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Human-readable sidecar form.
+    pub fn to_json(&self) -> Result<String, ClassiferError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))
+    }
+
+    pub fn from_json(input: &str) -> Result<Self, ClassiferError> {
+        serde_json::from_str(input).map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))
+    }
+
+    /// Compact binary sidecar form.
+    pub fn to_binary(&self) -> Result<Vec<u8>, ClassiferError> {
+        bincode::serialize(self).map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, ClassiferError> {
+        bincode::deserialize(bytes).map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))
+    }
+
+    pub fn save_json(&self, path: impl AsRef<Path>) -> Result<(), ClassiferError> {
+        std::fs::write(path, self.to_json()?).map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))
+    }
+
+    pub fn save_binary(&self, path: impl AsRef<Path>) -> Result<(), ClassiferError> {
+        std::fs::write(path, self.to_binary()?).map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))
+    }
+
+    pub fn load_json(path: impl AsRef<Path>) -> Result<Self, ClassiferError> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))?;
+        Self::from_json(&raw)
+    }
+
+    pub fn load_binary(path: impl AsRef<Path>) -> Result<Self, ClassiferError> {
+        let raw =
+            std::fs::read(path).map_err(|e| ClassiferError::ClassificationFailed(e.to_string()))?;
+        Self::from_binary(&raw)
+    }
+
+    /// Finds the record for `(page, type_tag)`, if this structure has one.
+    pub fn record_for(&self, page: i32, type_tag: &str) -> Option<&PageRecord> {
+        self.pages
+            .iter()
+            .find(|record| record.page == page && record.type_tag == type_tag)
+    }
+}
+
+/// Maps a type's tag to its parent/pair tags, used to fill in
+/// [PageRecord::parent_tag]/[PageRecord::pair_tag] without re-walking the
+/// live `ConcretePageType` tree per page.
+pub(crate) type TagRegistry = HashMap<String, (Option<String>, Option<String>)>;
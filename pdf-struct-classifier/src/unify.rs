@@ -0,0 +1,420 @@
+//! `ConcreteInferredPage` exists to represent objects whose type isn't
+//! pinned by a key, but nothing before this module actually infers it —
+//! `ConcreteInferredPage::new` just wraps whatever concrete object it's
+//! handed. [`solve`] is a small Hindley-Milner-flavoured constraint
+//! solver: one type variable per node plus one per `(parent, expected
+//! child type)` slot, backed by a `Vec<usize>`-based union-find with path
+//! compression and union-by-rank. `Key` pages seed their variable directly
+//! (their type isn't in question); every other node is unified with
+//! whichever of its parent's expected-child slots elimination narrows it
+//! to, and the binding propagates through the union. Whatever's still
+//! unbound at the fixpoint comes back as [`Resolved::Unknown`], for the
+//! caller to fall back to raw classification confidence.
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+
+use pdf_struct_traits::TypeInformation;
+
+use crate::instances::{ConcretePageType, ConcreteRoot};
+
+/// A node's resolved type once [`solve`] reaches a fixpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Resolved {
+    Bound(TypeInformation),
+    Unknown,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UnifyError {
+    #[error("conflicting bindings `{first}` vs `{second}` ({context})")]
+    Conflict {
+        first: &'static str,
+        second: &'static str,
+        context: String,
+    },
+    #[error("cycle detected while walking the parent/children graph at `{0}`")]
+    Cycle(&'static str),
+    #[error("node `{0}` is reachable through more than one parent")]
+    MultipleParents(&'static str),
+}
+
+fn conflict(first: TypeInformation, second: TypeInformation, context: String) -> UnifyError {
+    UnifyError::Conflict {
+        first: first.ident,
+        second: second.ident,
+        context,
+    }
+}
+
+/// A `Vec<usize>`-backed union-find over type variables, with path
+/// compression and union-by-rank.
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    binding: Vec<Option<TypeInformation>>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+            binding: vec![None; len],
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    /// Unifies `a` and `b`'s variables, merging by rank. If both sides are
+    /// already bound to different concrete types, returns them instead of
+    /// unifying — the caller knows the edge involved and reports it.
+    fn union(&mut self, a: usize, b: usize) -> Result<(), (TypeInformation, TypeInformation)> {
+        let ra = self.find(a);
+        let rb = self.find(b);
+        if ra == rb {
+            return Ok(());
+        }
+
+        let merged = match (self.binding[ra].clone(), self.binding[rb].clone()) {
+            (Some(x), Some(y)) if x.id != y.id => return Err((x, y)),
+            (Some(x), _) => Some(x),
+            (_, Some(y)) => Some(y),
+            (None, None) => None,
+        };
+
+        let (big, small) = if self.rank[ra] >= self.rank[rb] {
+            (ra, rb)
+        } else {
+            (rb, ra)
+        };
+        self.parent[small] = big;
+        if self.rank[ra] == self.rank[rb] {
+            self.rank[big] += 1;
+        }
+        self.binding[big] = merged;
+        Ok(())
+    }
+
+    /// Binds `x`'s variable directly to `ty`, failing if it's already bound
+    /// to something else.
+    fn bind(
+        &mut self,
+        x: usize,
+        ty: TypeInformation,
+    ) -> Result<(), (TypeInformation, TypeInformation)> {
+        let root = self.find(x);
+        match self.binding[root].clone() {
+            Some(existing) if existing.id != ty.id => Err((existing, ty)),
+            _ => {
+                self.binding[root] = Some(ty);
+                Ok(())
+            }
+        }
+    }
+
+    fn binding_of(&mut self, x: usize) -> Option<TypeInformation> {
+        let root = self.find(x);
+        self.binding[root].clone()
+    }
+}
+
+/// One node discovered while walking `root`'s tree.
+struct Node {
+    info: TypeInformation,
+    is_key: bool,
+    expected_children: Vec<TypeInformation>,
+    parent_var: Option<usize>,
+}
+
+/// Walks `root`'s tree collecting one [`Node`] per reachable object,
+/// locking exactly one guard at a time (the outer `ConcretePageType` guard
+/// is dropped before the inner `ConcreteObject` guard is taken). Rejects a
+/// cycle — the same node reached while still on the path to it — and a
+/// node reachable through more than one distinct parent, rather than
+/// looping forever or silently picking one parent.
+#[allow(clippy::too_many_arguments)]
+fn visit(
+    node: &Arc<RwLock<ConcretePageType>>,
+    parent_var: Option<usize>,
+    parent_id: Option<TypeId>,
+    nodes: &mut Vec<Node>,
+    var_of: &mut HashMap<TypeId, usize>,
+    claimed_by: &mut HashMap<TypeId, TypeId>,
+    on_path: &mut HashSet<TypeId>,
+) -> Result<(), UnifyError> {
+    let (is_key, inner) = {
+        let page_type = node.read().unwrap();
+        let is_key = matches!(&*page_type, ConcretePageType::Key(_));
+        (is_key, page_type.inner())
+    };
+
+    let (info, expected_children, children) = {
+        let obj = inner.read().unwrap();
+        (
+            obj.obj_type.clone(),
+            obj.expected_children.clone(),
+            obj.children.clone(),
+        )
+    };
+
+    if let Some(parent_id) = parent_id {
+        match claimed_by.get(&info.id) {
+            Some(&existing) if existing != parent_id => {
+                return Err(UnifyError::MultipleParents(info.ident));
+            }
+            _ => {
+                claimed_by.insert(info.id, parent_id);
+            }
+        }
+    }
+
+    if var_of.contains_key(&info.id) {
+        return if on_path.contains(&info.id) {
+            Err(UnifyError::Cycle(info.ident))
+        } else {
+            Ok(())
+        };
+    }
+
+    on_path.insert(info.id);
+    let this_var = nodes.len();
+    var_of.insert(info.id, this_var);
+    nodes.push(Node {
+        info: info.clone(),
+        is_key,
+        expected_children,
+        parent_var,
+    });
+
+    for child in &children {
+        visit(
+            child,
+            Some(this_var),
+            Some(info.id),
+            nodes,
+            var_of,
+            claimed_by,
+            on_path,
+        )?;
+    }
+
+    on_path.remove(&info.id);
+    Ok(())
+}
+
+fn collect_nodes(root: &ConcreteRoot) -> Result<Vec<Node>, UnifyError> {
+    let mut nodes = Vec::new();
+    let mut var_of = HashMap::new();
+    let mut claimed_by = HashMap::new();
+    let mut on_path = HashSet::new();
+
+    for child in &root.children {
+        visit(
+            child,
+            None,
+            None,
+            &mut nodes,
+            &mut var_of,
+            &mut claimed_by,
+            &mut on_path,
+        )?;
+    }
+
+    Ok(nodes)
+}
+
+/// Resolves every node reachable from `root` to a concrete
+/// [`TypeInformation`], seeding `Key` pages directly and narrowing every
+/// other node by elimination against its parent's `expected_children`
+/// until no more progress can be made. The result is keyed by each node's
+/// own `TypeId` — the same identity [`crate::instances::ObjectCache`]
+/// itself is keyed by, since this tree can never hold two distinct nodes
+/// sharing one.
+pub fn solve(root: &ConcreteRoot) -> Result<HashMap<TypeId, Resolved>, UnifyError> {
+    let nodes = collect_nodes(root)?;
+
+    let mut children_of: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (var, node) in nodes.iter().enumerate() {
+        if let Some(parent_var) = node.parent_var {
+            children_of.entry(parent_var).or_default().push(var);
+        }
+    }
+
+    // One variable per `(parent, expected child type)` slot, each bound to
+    // its own known type immediately — there's no ambiguity in what a slot
+    // *could* be, only in which attached child fills it.
+    let mut slot_order: Vec<(usize, TypeInformation)> = Vec::new();
+    let mut slot_var_by_type: HashMap<(usize, TypeId), usize> = HashMap::new();
+    for (parent_var, node) in nodes.iter().enumerate() {
+        for expected in &node.expected_children {
+            slot_var_by_type
+                .entry((parent_var, expected.id))
+                .or_insert_with(|| {
+                    let var = nodes.len() + slot_order.len();
+                    slot_order.push((parent_var, expected.clone()));
+                    var
+                });
+        }
+    }
+
+    let mut uf = UnionFind::new(nodes.len() + slot_order.len());
+
+    for (parent_var, ty) in &slot_order {
+        let var = slot_var_by_type[&(*parent_var, ty.id)];
+        uf.bind(var, ty.clone())
+            .map_err(|(a, b)| conflict(a, b, format!("binding slot for `{}`", ty.ident)))?;
+    }
+
+    for (var, node) in nodes.iter().enumerate() {
+        if node.is_key {
+            uf.bind(var, node.info.clone()).map_err(|(a, b)| {
+                conflict(a, b, format!("seeding key page `{}`", node.info.ident))
+            })?;
+        }
+    }
+
+    loop {
+        let mut changed = false;
+
+        for (&parent_var, child_vars) in &children_of {
+            let mut claimed: HashSet<TypeId> = HashSet::new();
+            let mut unbound: Vec<usize> = Vec::new();
+            for &child_var in child_vars {
+                match uf.binding_of(child_var) {
+                    Some(ty) => {
+                        claimed.insert(ty.id);
+                    }
+                    None => unbound.push(child_var),
+                }
+            }
+
+            if unbound.len() != 1 {
+                continue;
+            }
+
+            let remaining: Vec<&TypeInformation> = nodes[parent_var]
+                .expected_children
+                .iter()
+                .filter(|ty| !claimed.contains(&ty.id))
+                .collect();
+
+            if remaining.len() != 1 {
+                continue;
+            }
+
+            let child_var = unbound[0];
+            let slot_var = slot_var_by_type[&(parent_var, remaining[0].id)];
+            uf.union(child_var, slot_var).map_err(|(a, b)| {
+                conflict(
+                    a,
+                    b,
+                    format!(
+                        "unifying `{}` with its parent's expected slot",
+                        nodes[child_var].info.ident
+                    ),
+                )
+            })?;
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let mut resolved: HashMap<TypeId, Resolved> = HashMap::new();
+    for (var, node) in nodes.iter().enumerate() {
+        match uf.binding_of(var) {
+            Some(ty) if ty.id != node.info.id => {
+                return Err(conflict(
+                    ty,
+                    node.info.clone(),
+                    format!(
+                        "`{}`'s solved type disagrees with how it was actually constructed",
+                        node.info.ident
+                    ),
+                ));
+            }
+            Some(ty) => {
+                resolved.insert(node.info.id, Resolved::Bound(ty));
+            }
+            None => {
+                resolved.insert(node.info.id, Resolved::Unknown);
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct A;
+    struct B;
+    struct C;
+
+    fn ty_of<T: 'static>(ident: &'static str) -> TypeInformation {
+        TypeInformation {
+            id: TypeId::of::<T>(),
+            ident,
+        }
+    }
+
+    #[test]
+    fn union_propagates_a_binding_to_every_merged_variable() {
+        let mut uf = UnionFind::new(3);
+        let a = ty_of::<A>("A");
+
+        uf.bind(0, a.clone()).unwrap();
+        uf.union(0, 1).unwrap();
+        uf.union(1, 2).unwrap();
+
+        assert_eq!(uf.binding_of(0), Some(a.clone()));
+        assert_eq!(uf.binding_of(1), Some(a.clone()));
+        assert_eq!(uf.binding_of(2), Some(a));
+    }
+
+    #[test]
+    fn union_of_two_distinct_bindings_reports_the_conflict() {
+        let mut uf = UnionFind::new(2);
+        let a = ty_of::<A>("A");
+        let b = ty_of::<B>("B");
+
+        uf.bind(0, a.clone()).unwrap();
+        uf.bind(1, b.clone()).unwrap();
+
+        let err = uf.union(0, 1).unwrap_err();
+        assert_eq!(err, (a, b));
+    }
+
+    #[test]
+    fn bind_twice_with_the_same_type_is_idempotent() {
+        let mut uf = UnionFind::new(1);
+        let a = ty_of::<A>("A");
+
+        uf.bind(0, a.clone()).unwrap();
+        uf.bind(0, a.clone()).unwrap();
+
+        assert_eq!(uf.binding_of(0), Some(a));
+    }
+
+    #[test]
+    fn unrelated_variables_stay_unbound_until_unioned() {
+        let mut uf = UnionFind::new(3);
+        let c = ty_of::<C>("C");
+
+        uf.bind(0, c).unwrap();
+
+        assert_eq!(uf.binding_of(1), None);
+        assert_eq!(uf.binding_of(2), None);
+    }
+}
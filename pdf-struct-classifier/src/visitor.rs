@@ -0,0 +1,111 @@
+//! Every traversal over a resolved tree otherwise re-implements the same
+//! nested `read().unwrap().inner().read().unwrap()` dance found in
+//! `ConcreteObject::add_child`/`collect_children_from_cache` and
+//! `ConcreteRoot::validate_root_child` — verbose, and deadlock-prone the
+//! moment two guards end up held at once. [`walk`] does that locking once,
+//! one guard at a time, and hands callbacks an already-dereferenced
+//! [`ConcreteObject`]/[`ConcretePair`] so they never need to touch an
+//! `Arc<RwLock<...>>` themselves.
+
+use std::sync::{Arc, RwLock};
+
+use crate::instances::{ConcreteObject, ConcretePageType, ConcretePair, ConcreteRoot};
+
+/// Whether [`walk`] visits a node before or after its children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WalkOrder {
+    PreOrder,
+    PostOrder,
+}
+
+/// Returned by a [`Visitor`] callback to say whether [`walk`] should keep
+/// going or stop immediately, without visiting anything else.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ControlFlow {
+    Continue,
+    Stop,
+}
+
+/// Callbacks [`walk`] invokes as it descends a resolved tree. Both methods
+/// default to [`ControlFlow::Continue`], so implementors only override the
+/// one(s) they care about.
+pub trait Visitor {
+    /// Called for every page node, with its already-locked, already
+    /// type-erased-no-more data and its depth from the root (0 for a
+    /// direct root child).
+    fn visit_object(&mut self, object: &ConcreteObject, depth: usize) -> ControlFlow {
+        let _ = (object, depth);
+        ControlFlow::Continue
+    }
+
+    /// Called once per `Pair` node, in addition to `visit_object` for its
+    /// underlying [`ConcreteObject`]. Always called before the node's
+    /// children, whichever [`WalkOrder`] is in effect.
+    fn visit_pair(&mut self, pair: &ConcretePair, depth: usize) -> ControlFlow {
+        let _ = (pair, depth);
+        ControlFlow::Continue
+    }
+}
+
+/// Walks every node reachable from `root`'s children, depth-first, in
+/// `order`, stopping as soon as a [`Visitor`] callback returns
+/// [`ControlFlow::Stop`].
+pub fn walk<V: Visitor>(root: &ConcreteRoot, order: WalkOrder, visitor: &mut V) -> ControlFlow {
+    for child in &root.children {
+        if let ControlFlow::Stop = walk_node(child, 0, order, visitor) {
+            return ControlFlow::Stop;
+        }
+    }
+    ControlFlow::Continue
+}
+
+/// Visits a single node and recurses into its children. Never holds more
+/// than one `RwLock` guard at a time: the outer `ConcretePageType` guard is
+/// dropped before the inner `ConcreteObject` guard is taken, and that guard
+/// in turn is dropped before recursing into children.
+fn walk_node<V: Visitor>(
+    node: &Arc<RwLock<ConcretePageType>>,
+    depth: usize,
+    order: WalkOrder,
+    visitor: &mut V,
+) -> ControlFlow {
+    let (pair, inner) = {
+        let page_type = node.read().unwrap();
+        let pair = match &*page_type {
+            ConcretePageType::Pair(pair) => Some(pair.clone()),
+            ConcretePageType::Key(_) | ConcretePageType::Inferred(_) => None,
+        };
+        (pair, page_type.inner())
+    };
+
+    if let Some(pair) = &pair {
+        if let ControlFlow::Stop = visitor.visit_pair(pair, depth) {
+            return ControlFlow::Stop;
+        }
+    }
+
+    let children = {
+        let object = inner.read().unwrap();
+        if order == WalkOrder::PreOrder {
+            if let ControlFlow::Stop = visitor.visit_object(&object, depth) {
+                return ControlFlow::Stop;
+            }
+        }
+        object.children.clone()
+    };
+
+    for child in &children {
+        if let ControlFlow::Stop = walk_node(child, depth + 1, order, visitor) {
+            return ControlFlow::Stop;
+        }
+    }
+
+    if order == WalkOrder::PostOrder {
+        let object = inner.read().unwrap();
+        if let ControlFlow::Stop = visitor.visit_object(&object, depth) {
+            return ControlFlow::Stop;
+        }
+    }
+
+    ControlFlow::Continue
+}
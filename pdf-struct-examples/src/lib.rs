@@ -1,6 +1,6 @@
 #![allow(unused)]
 
-use pdf_struct_macros::{object, root};
+use pdf_struct_macros::{object, root, Object};
 use pdf_struct_traits::Pattern;
 use pdf_struct_traits::*;
 use pdf_struct_traits::{Classify, Extract};
@@ -41,6 +41,17 @@ struct DataTable;
 
 struct Document;
 
+// Same `Diagram`/`DataTable` pairing as above, expressed through
+// `#[derive(Object)]` instead of `#[object(...)]` — exercises the derive
+// macro's `#[pair(..., patterns = [...])]` attribute.
+#[derive(Object, Debug, Clone)]
+#[pair(ReportTable, sequence = "first", patterns = [Pattern::from_pair::<ReportFigure, ReportTable>()])]
+struct ReportFigure;
+
+#[derive(Object, Debug, Clone)]
+#[pair(ReportFigure, sequence = "last", patterns = [Pattern::from_pair::<ReportFigure, ReportTable>()])]
+struct ReportTable;
+
 #[derive(Debug, thiserror::Error)]
 enum Error {}
 
@@ -81,6 +92,21 @@ impl_classify_and_extract!(SubChapter);
 impl_classify_and_extract!(Diagram);
 impl_classify_and_extract!(DataTable);
 impl_classify_and_extract!(ChapterMetadata);
+impl_classify_and_extract!(ReportFigure);
+impl_classify_and_extract!(ReportTable);
+
+#[test]
+fn derive_object_pair_patterns() {
+    let patterns = <ReportFigure as PairWith<ReportTable>>::PATTERNS;
+    assert_eq!(patterns.len(), 1);
+    match &patterns[0] {
+        Pattern::Pair { first, second } => {
+            assert_eq!(*first, ReportFigure::TYPE);
+            assert_eq!(*second, ReportTable::TYPE);
+        }
+        _ => panic!("expected Pattern::Pair"),
+    }
+}
 
 #[test]
 fn test() {
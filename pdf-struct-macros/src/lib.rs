@@ -1,11 +1,12 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{
-    Expr, Ident, ItemStruct, Token, bracketed, parenthesized,
+    bracketed, parenthesized,
     parse::Parse,
     parse_macro_input,
     punctuated::Punctuated,
     token::{Bracket, Paren},
+    DeriveInput, Expr, Ident, ItemStruct, LitStr, Token,
 };
 
 struct ObjectArgs {
@@ -241,6 +242,9 @@ pub fn object(args: TokenStream, input: TokenStream) -> TokenStream {
 
         #object_impl
 
+        impl Encodable for #struct_name {}
+        impl Decodable for #struct_name {}
+
         #(#generated_impls)*
     };
 
@@ -260,3 +264,237 @@ pub fn root(_args: TokenStream, input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// `#[pair(Table, sequence = "last", patterns = [...])]` — a bare pair
+/// type, with an optional `sequence = "first" | "last" | "none"`
+/// (defaulting to `"first"`, same as the `#[object]` attribute macro's own
+/// default) and an optional `patterns = [...]` list, parsed the same way
+/// `#[object(patterns = [...])]` parses it.
+struct PairAttr {
+    pair_type: Ident,
+    sequence: PairSequenceType,
+    patterns: Vec<Expr>,
+}
+
+impl Parse for PairAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let pair_type: Ident = input.parse()?;
+        let mut sequence = PairSequenceType::First;
+        let mut patterns = Vec::new();
+
+        while input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+
+            if key == "sequence" {
+                let value: LitStr = input.parse()?;
+                sequence = match value.value().as_str() {
+                    "first" => PairSequenceType::First,
+                    "last" => PairSequenceType::Last,
+                    "none" => PairSequenceType::None,
+                    _ => {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            "Expected \"first\", \"last\", or \"none\"",
+                        ));
+                    }
+                };
+            } else if key == "patterns" {
+                // patterns = [ Pattern::Pair { first: A::TYPE, second: B::TYPE }, ... ]
+                if input.peek(Bracket) {
+                    let content;
+                    bracketed!(content in input);
+                    let pattern_exprs: Punctuated<Expr, Token![,]> =
+                        content.parse_terminated(Expr::parse, Token![,])?;
+                    patterns = pattern_exprs.into_iter().collect();
+                } else {
+                    return Err(syn::Error::new(key.span(), "Expected bracket(s)"));
+                }
+            } else {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "Expected `sequence` or `patterns`",
+                ));
+            }
+        }
+
+        Ok(PairAttr {
+            pair_type,
+            sequence,
+            patterns,
+        })
+    }
+}
+
+/// Derives `Object` and the `Encodable`/`Decodable`/`Parent`/`Child`/
+/// `PairWith` impls that go with it (or, with `#[object(root)]`, only
+/// `impl Root`) from struct-level helper attributes, as an alternative to the
+/// `#[object(...)]` attribute macro above for callers who'd rather keep
+/// their struct definition free of macro arguments:
+///
+/// ```ignore
+/// #[derive(Object)]
+/// #[object(key)]
+/// #[parent(Chapter)]
+/// #[child(Diagram)]
+/// #[pair(DataTable, sequence = "last", patterns = [Pattern::from_pair::<SubChapter, DataTable>()])]
+/// struct SubChapter { .. }
+/// ```
+///
+/// `#[parent(X)]`/`#[child(X)]` each also emit a compile-time assertion
+/// that `X` implements the complementary marker trait (`X: Parent` for a
+/// declared parent, `X: Child` for a declared child) — the symmetry the
+/// `Parent`/`Child` relationship is supposed to hold, caught at compile
+/// time instead of silently producing a one-sided tree.
+#[proc_macro_derive(Object, attributes(object, parent, child, pair))]
+pub fn derive_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+
+    let mut page_type = PageType::Inferred;
+    let mut is_root = false;
+    let mut parent_type: Option<Ident> = None;
+    let mut child_types: Vec<Ident> = Vec::new();
+    let mut pair_type: Option<Ident> = None;
+    let mut pair_sequence = quote! { PairSequence::First };
+    let mut pair_patterns: Vec<Expr> = Vec::new();
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("object") {
+            let kind: Ident = match attr.parse_args() {
+                Ok(kind) => kind,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            match kind.to_string().as_str() {
+                "key" => page_type = PageType::Key,
+                "inferred" => page_type = PageType::Inferred,
+                "root" => is_root = true,
+                _ => {
+                    return syn::Error::new(kind.span(), "Expected `key`, `inferred`, or `root`")
+                        .to_compile_error()
+                        .into();
+                }
+            }
+        } else if attr.path().is_ident("parent") {
+            parent_type = match attr.parse_args() {
+                Ok(parent) => Some(parent),
+                Err(err) => return err.to_compile_error().into(),
+            };
+        } else if attr.path().is_ident("child") {
+            match attr.parse_args() {
+                Ok(child) => child_types.push(child),
+                Err(err) => return err.to_compile_error().into(),
+            }
+        } else if attr.path().is_ident("pair") {
+            let parsed: PairAttr = match attr.parse_args() {
+                Ok(parsed) => parsed,
+                Err(err) => return err.to_compile_error().into(),
+            };
+            pair_sequence = match parsed.sequence {
+                PairSequenceType::First => quote! { PairSequence::First },
+                PairSequenceType::Last => quote! { PairSequence::Last },
+                PairSequenceType::None => quote! { PairSequence::None },
+            };
+            pair_patterns = parsed.patterns;
+            pair_type = Some(parsed.pair_type);
+        }
+    }
+
+    // A root marks the document's entry point, not a page — same as the
+    // separate `#[root]` attribute macro, it gets only `impl Root`.
+    if is_root {
+        return TokenStream::from(quote! {
+            impl Root for #struct_name {}
+        });
+    }
+
+    let mut generated_impls = Vec::new();
+    let mut assertions = Vec::new();
+
+    let parent_ty = match &parent_type {
+        Some(parent) => quote! { #parent },
+        None => quote! { () },
+    };
+    if let Some(parent) = &parent_type {
+        generated_impls.push(quote! {
+            impl Child for #struct_name {}
+        });
+        assertions.push(quote! {
+            const _: fn() = || {
+                fn __pdf_struct_assert_is_parent<T: Parent>() {}
+                __pdf_struct_assert_is_parent::<#parent>();
+            };
+        });
+    }
+
+    if !child_types.is_empty() {
+        generated_impls.push(quote! {
+            impl Parent for #struct_name {}
+        });
+    }
+    for child in &child_types {
+        assertions.push(quote! {
+            const _: fn() = || {
+                fn __pdf_struct_assert_is_child<T: Child>() {}
+                __pdf_struct_assert_is_child::<#child>();
+            };
+        });
+    }
+
+    let children_array = if child_types.is_empty() {
+        quote! { &[] }
+    } else {
+        quote! { &[#(#child_types::TYPE),*] }
+    };
+
+    let pair_ty = match &pair_type {
+        Some(pair) => quote! { #pair },
+        None => quote! { () },
+    };
+    if let Some(pair) = &pair_type {
+        let pattern_items = pair_patterns.iter().map(|expr| quote! { #expr });
+        let patterns_array = if pair_patterns.is_empty() {
+            quote! { &[] }
+        } else {
+            quote! { &[#(#pattern_items),*] }
+        };
+        generated_impls.push(quote! {
+            impl PairWith<#pair> for #struct_name {
+                const SEQUENCE: PairSequence = #pair_sequence;
+                const PATTERNS: &'static [Pattern] = #patterns_array;
+            }
+        });
+    }
+
+    let key_page = page_type == PageType::Key;
+    let inferred_page = page_type == PageType::Inferred;
+
+    let object_impl = quote! {
+        impl Object for #struct_name {
+            const CHILDREN: &'static [TypeInformation] = #children_array;
+            const TYPE: TypeInformation = TypeInformation {
+                id: std::any::TypeId::of::<Self>(),
+                ident: stringify!(#struct_name),
+            };
+            const KEY_PAGE: bool = #key_page;
+            const INFERRED_PAGE: bool = #inferred_page;
+
+            type Parent = #parent_ty;
+            type Pair = #pair_ty;
+        }
+    };
+
+    let expanded = quote! {
+        #object_impl
+
+        impl Encodable for #struct_name {}
+        impl Decodable for #struct_name {}
+
+        #(#generated_impls)*
+
+        #(#assertions)*
+    };
+
+    TokenStream::from(expanded)
+}
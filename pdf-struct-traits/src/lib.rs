@@ -17,6 +17,15 @@ pub enum Pattern {
         first: TypeInformation,
         second: TypeInformation,
     },
+    /// An ordered run of patterns that must all match, in order, for the
+    /// pattern as a whole to apply (e.g. Diagram, then Table, then Caption).
+    Sequence(&'static [Pattern]),
+    /// A pattern that may or may not be present; its absence doesn't fail
+    /// the pattern it's nested within.
+    Optional(&'static Pattern),
+    /// A pattern that may match one or more times in a row (e.g. a run of
+    /// Figure pages of unknown length).
+    Repetition(&'static Pattern),
 }
 
 impl Pattern {
@@ -34,6 +43,21 @@ impl Pattern {
             second: U::TYPE,
         }
     }
+
+    /// Matches `patterns` in order, all of which must apply.
+    pub const fn sequence(patterns: &'static [Pattern]) -> Self {
+        Self::Sequence(patterns)
+    }
+
+    /// Wraps `pattern` so its absence doesn't fail whatever it's nested in.
+    pub const fn optional(pattern: &'static Pattern) -> Self {
+        Self::Optional(pattern)
+    }
+
+    /// Wraps `pattern` so it may match one or more times in a row.
+    pub const fn repeated(pattern: &'static Pattern) -> Self {
+        Self::Repetition(pattern)
+    }
 }
 
 /// Indicates the position of an object relative to the order of pages
@@ -72,6 +96,21 @@ pub trait KeyPage: Object {}
 /// Self does NOT have be explicitly classified to be constructed
 pub trait InferredPage: Object {}
 
+/// Context `Self` can expose down to its `Child` subtree — resources, a
+/// numbering base, crop/media geometry, style — the same "inherited down
+/// the page tree" semantics `pdf::object::PageTree` uses for its own
+/// resources and boxes. `C` names the kind of context being inherited, so
+/// one type can implement this for several independent `C`s at once.
+///
+/// `inherit` defaults to `None`, meaning "I don't set or override this —
+/// keep walking up to the next ancestor"; a type only overrides it where
+/// it actually wants to define or override that context for its subtree.
+pub trait Inheritable<C>: Object {
+    fn inherit(&self) -> Option<C> {
+        None
+    }
+}
+
 /// Signifies that a struct represents the root document
 pub trait Root {}
 
@@ -166,12 +205,22 @@ impl Extract for () {
     }
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TypeInformation {
     pub id: TypeId,
     pub ident: &'static str,
 }
 
+/// A chapter/page coordinate, addressing a page relative to its chapter's
+/// own `KeyPage` rather than as one flat document-wide index — the same
+/// model as mupdf's `fz_location`, where only the requested chapter needs
+/// to be decoded to resolve it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Location {
+    pub chapter: usize,
+    pub page: usize,
+}
+
 /// Indicates that Self is an in-code representation of a page
 /// within a PDF document.
 pub trait Object
@@ -181,6 +230,28 @@ where
     const CHILDREN: &'static [TypeInformation] = &[];
     const TYPE: TypeInformation;
 
+    /// Whether `Self` is a [`KeyPage`] — explicitly classified rather than
+    /// inferred from its parent's expectations.
+    const KEY_PAGE: bool = false;
+    /// Whether `Self` is an [`InferredPage`] — its type is narrowed from
+    /// its parent's `expected_children` rather than classified directly.
+    const INFERRED_PAGE: bool = false;
+
     type Parent: Parent = ();
     type Pair: PairWith<Self> = ();
 }
+
+/// Marks an [Object] as serializable into the on-disk document-structure
+/// sidecar. Implemented automatically by the `#[object]` attribute macro and
+/// by `#[derive(Object)]` (for non-root types), so every declared page type carries a stable, human-readable tag that
+/// survives across process runs (unlike [TypeId](std::any::TypeId), which
+/// does not).
+pub trait Encodable: Object {
+    /// The tag a serialized sidecar uses to identify this type. Defaults to
+    /// the struct's identifier.
+    const TAG: &'static str = Self::TYPE.ident;
+}
+
+/// The reciprocal of [Encodable]: marks an [Object] as reconstructible from
+/// a sidecar record carrying its [Encodable::TAG].
+pub trait Decodable: Encodable {}
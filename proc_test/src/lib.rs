@@ -32,17 +32,3 @@ struct DataTable;
 
 #[object(root)]
 struct Document;
-
-fn config() {
-    use pdf_parser_v3::{config::*, pattern::Pattern};
-
-    let builder = Config::builder()
-        .with_root::<Document>()
-        .with_key::<Chapter>()
-        .with_key::<SubChapter>()
-        .with_inferred::<Diagram>()
-        .with_inferred::<DataTable>()
-        .with_pattern(Pattern::from_pair::<Diagram, DataTable>())
-        .build();
-    
-}